@@ -1,7 +1,7 @@
 use crate::expression_literal_value::LiteralValue;
 
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::rc::Rc;
 
 #[cfg(test)]
@@ -11,6 +11,8 @@ mod tests;
 pub struct Environment {
     pub values: HashMap<String, LiteralValue>,
     pub enclosing: Option<Rc<RefCell<Environment>>>,
+    // names in values that can't be reassigned
+    constants: HashSet<String>,
 }
 
 impl Environment {
@@ -18,17 +20,32 @@ impl Environment {
         Environment {
             values: HashMap::new(),
             enclosing: None,
+            constants: HashSet::new(),
         }
     }
 
-    pub fn define(&mut self, name: String, value: LiteralValue) {
+    pub fn define(&mut self, name: String, value: LiteralValue) -> Result<(), String> {
+        if self.constants.contains(&name) {
+            return Err(format!("Cannot redefine constant {}", name));
+        }
+
         self.values.insert(name, value);
+        Ok(())
+    }
+
+    pub fn define_constant(&mut self, name: String, value: LiteralValue) -> Result<(), String> {
+        self.define(name.clone(), value)?;
+        self.constants.insert(name);
+        Ok(())
     }
 
     pub fn assign(&mut self, name: String, value: LiteralValue) -> Result<(), String> {
         match self.values.get_key_value(&name) {
+            Some(_) if self.constants.contains(&name) => {
+                Err(format!("Cannot assign to constant {}", name))
+            }
             Some(_) => {
-                self.define(name, value);
+                self.values.insert(name, value);
                 Ok(())
             }
 
@@ -48,4 +65,46 @@ impl Environment {
             },
         }
     }
+
+    pub fn get_at(&self, distance: usize, name: &str) -> Result<LiteralValue, String> {
+        if distance == 0 {
+            // module globals sit inside of an environment holding the natives, those are found by name
+            return match self.values.get(name) {
+                Some(value) => Ok(value.clone()),
+                None => self.get(name),
+            };
+        }
+
+        match &self.enclosing {
+            Some(enclosing_environment) => {
+                enclosing_environment.borrow().get_at(distance - 1, name)
+            }
+            _ => Err(format!("Undefined variable {}", name)),
+        }
+    }
+
+    pub fn assign_at(
+        &mut self,
+        distance: usize,
+        name: String,
+        value: LiteralValue,
+    ) -> Result<(), String> {
+        if distance == 0 {
+            return match self.values.get_key_value(&name) {
+                Some(_) if self.constants.contains(&name) => {
+                    Err(format!("Cannot assign to constant {}", name))
+                }
+                Some(_) => {
+                    self.values.insert(name, value);
+                    Ok(())
+                }
+                None => self.assign(name, value),
+            };
+        }
+
+        match &self.enclosing {
+            Some(env) => env.borrow_mut().assign_at(distance - 1, name, value),
+            _ => Err(format!("Variable does not exist {}", name)),
+        }
+    }
 }
@@ -1,9 +1,13 @@
 use crate::compare_values;
 use crate::environment::Environment;
 use crate::expression_literal_value::LiteralValue::{self, *};
+use crate::expression_literal_value::MapKey;
+use crate::interpreter::{Interpreter, RuntimeError};
+use crate::statement::{Parameter, Statement};
 use crate::token::{Token, TokenType::*};
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
 use std::rc::Rc;
 use std::string::String;
@@ -31,20 +35,81 @@ pub enum Expression {
     },
     Variable {
         token: Token,
+        depth: Option<usize>,
     },
     Assign {
-        name: String,
+        name: Token,
         value: Box<Expression>,
+        depth: Option<usize>,
     },
     Logical {
         left: Box<Expression>,
         operator: Token,
         right: Box<Expression>,
     },
+    Conditional {
+        condition: Box<Expression>,
+        then_branch: Box<Expression>,
+        else_branch: Box<Expression>,
+    },
+    Range {
+        start: Box<Expression>,
+        operator: Token,
+        end: Box<Expression>,
+        step: Option<Box<Expression>>,
+    },
     Call {
         callee: Box<Expression>,
         paren: Token,
         arguments: Vec<Expression>,
+        named: Vec<(Token, Expression)>,
+    },
+    Get {
+        object: Box<Expression>,
+        name: Token,
+    },
+    Set {
+        object: Box<Expression>,
+        name: Token,
+        value: Box<Expression>,
+        operator: Option<Token>,
+    },
+    List {
+        elements: Vec<Expression>,
+    },
+    Map {
+        entries: Vec<(Expression, Expression)>,
+    },
+    Index {
+        object: Box<Expression>,
+        bracket: Token,
+        index: Box<Expression>,
+    },
+    SetIndex {
+        object: Box<Expression>,
+        bracket: Token,
+        index: Box<Expression>,
+        value: Box<Expression>,
+        operator: Option<Token>,
+    },
+    Postfix {
+        target: Box<Expression>,
+        operator: Token,
+    },
+    This {
+        keyword: Token,
+        depth: Option<usize>,
+    },
+    Super {
+        keyword: Token,
+        method: Token,
+        depth: Option<usize>,
+    },
+    Lambda {
+        keyword: Token,
+        parameters: Vec<Parameter>,
+        return_type: Option<Token>,
+        body: Vec<Statement>,
     },
 }
 
@@ -75,6 +140,20 @@ impl Display for Expression {
                     right.to_string()
                 )
             }
+            Self::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => format!("(?: {} {} {})", condition, then_branch, else_branch),
+            Self::Range {
+                start,
+                operator,
+                end,
+                step,
+            } => match step {
+                Some(step) => format!("({} {} {} step {})", operator.lexeme, start, end, step),
+                None => format!("({} {} {})", operator.lexeme, start, end),
+            },
             Self::Grouping { group } => {
                 format!("(group {})", group.to_string())
             }
@@ -82,7 +161,7 @@ impl Display for Expression {
             Self::Unary { operator, right } => {
                 format!("({} {})", operator.lexeme, right.to_string())
             }
-            Self::Variable { token } => {
+            Self::Variable { token, depth: _ } => {
                 let lexeme = &token.lexeme;
                 if let Some(_) = token.literal {
                     format!("(defvar {} {})", lexeme, LiteralValue::from(token))
@@ -90,27 +169,114 @@ impl Display for Expression {
                     format!("(defvar {})", lexeme)
                 }
             }
-            Self::Assign { name, value } => format!("(= {} {})", name, value.to_string()),
+            Self::Assign {
+                name,
+                value,
+                depth: _,
+            } => format!("(= {} {})", name.lexeme, value.to_string()),
             Self::Call {
                 callee,
                 paren: _,
                 arguments,
+                named,
             } => {
                 let comma_separated = arguments
                     .iter()
                     .map(|val| val.to_string())
+                    .chain(
+                        named
+                            .iter()
+                            .map(|(name, val)| format!("{}:{}", name.lexeme, val)),
+                    )
                     .collect::<Vec<String>>()
                     .join(",");
 
                 format!("({} [{}])", callee, comma_separated)
             }
+            Self::Get { object, name } => format!("(get {} {})", object, name.lexeme),
+            Self::Set {
+                object,
+                name,
+                value,
+                operator,
+            } => format!(
+                "(set{} {} {} {})",
+                Self::compound_suffix(operator),
+                object,
+                name.lexeme,
+                value
+            ),
+            Self::List { elements } => {
+                let comma_separated = elements
+                    .iter()
+                    .map(|element| element.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                format!("(list [{}])", comma_separated)
+            }
+            Self::Map { entries } => {
+                let comma_separated = entries
+                    .iter()
+                    .map(|(key, value)| format!("{}:{}", key, value))
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                format!("(map [{}])", comma_separated)
+            }
+            Self::Index { object, index, .. } => format!("(index {} {})", object, index),
+            Self::SetIndex {
+                object,
+                index,
+                value,
+                operator,
+                ..
+            } => format!(
+                "(set-index{} {} {} {})",
+                Self::compound_suffix(operator),
+                object,
+                index,
+                value
+            ),
+            Self::Postfix { target, operator } => format!("(post{} {})", operator.lexeme, target),
+            Self::This { .. } => String::from("this"),
+            Self::Super { method, .. } => format!("(super {})", method.lexeme),
+            Self::Lambda { parameters, .. } => {
+                let comma_separated = parameters
+                    .iter()
+                    .map(|parameter| parameter.to_string())
+                    .collect::<Vec<String>>()
+                    .join(",");
+
+                format!("(fun [{}])", comma_separated)
+            }
         };
         write!(f, "{}", str)
     }
 }
 
 impl Expression {
-    pub fn evaluate(&self, environment: Rc<RefCell<Environment>>) -> Result<LiteralValue, String> {
+    // the innermost expression with a line locates a runtime error, a thrown value keeps its own
+    pub fn evaluate(
+        &self,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<LiteralValue, RuntimeError> {
+        self.evaluate_expression(environment).map_err(|mut error| {
+            if let (None, Some(line)) = (&error.value, self.line()) {
+                error.value = Some(ErrorValue {
+                    message: error.message.clone(),
+                    line,
+                });
+            }
+
+            error
+        })
+    }
+
+    fn evaluate_expression(
+        &self,
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<LiteralValue, RuntimeError> {
         match self {
             Self::Literal { value } => Ok(value.clone()),
             Self::Grouping { group } => group.evaluate(environment),
@@ -120,13 +286,22 @@ impl Expression {
                 match (&right, &operator.token_type) {
                     (IntValue(value), Minus) => Ok(IntValue(-value)),
                     (FValue(value), Minus) => Ok(FValue(-value)),
-                    (_, Minus) => Err(format!("Minus not implemented for {}", right.to_type())),
+                    (_, Minus) => {
+                        Err(format!("Minus not implemented for {}", right.to_type()).into())
+                    }
+                    (IntValue(value), Tilde) => Ok(IntValue(!value)),
+                    (_, Tilde) => Err(format!(
+                        "Tilde operation is only implemented for Int operands, got {}",
+                        right.to_type()
+                    )
+                    .into()),
                     (any, Bang) => Ok(LiteralValue::from(!bool::from(any))),
                     _ => Err(format!(
                         "Non unary operator {:?} is not implemented for {}",
                         operator.token_type,
                         right.to_type(),
-                    )),
+                    )
+                    .into()),
                 }
             }
             Self::Binary {
@@ -137,30 +312,14 @@ impl Expression {
                 let left = (*left).evaluate(environment.clone())?;
                 let right = (*right).evaluate(environment)?;
 
-                match &operator.token_type {
-                    Plus => left + right,
-                    Minus => left - right,
-                    Star => left * right,
-                    Slash => left / right,
-                    Greater => compare_values!(>, left, right),
-                    GreaterEqual => compare_values!(>=, left, right),
-                    Less => compare_values!(<, left, right),
-                    LessEqual => compare_values!(<=, left, right),
-                    BangEqual => compare_values!(!=, left, right),
-                    EqualEqual => compare_values!(==, left, right),
-                    _ => LiteralValue::not_implemented_error(
-                        &stringify!(operator.token_type),
-                        &left,
-                        &right,
-                    ),
-                }
-            }
-            Self::Variable { token } => environment.borrow_mut().get(&token.lexeme),
-            Self::Assign { name, value } => {
+                Ok(Self::binary_operation(left, operator, right)?)
+            }
+            Self::Variable { token, depth } => {
+                Ok(Self::look_up_variable(environment, &token.lexeme, depth)?)
+            }
+            Self::Assign { name, value, depth } => {
                 let value = value.evaluate(environment.clone())?;
-                environment
-                    .borrow_mut()
-                    .assign(name.clone(), value.clone())?; // temp fix
+                Self::assign_variable(environment, &name.lexeme, depth, value.clone())?;
                 Ok(value)
             }
             Self::Logical {
@@ -182,38 +341,435 @@ impl Expression {
 
                 right.evaluate(environment)
             }
+            Self::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                if bool::from(condition.evaluate(environment.clone())?) {
+                    then_branch.evaluate(environment)
+                } else {
+                    else_branch.evaluate(environment)
+                }
+            }
+            Self::Range {
+                start,
+                operator,
+                end,
+                step,
+            } => {
+                let start = Self::range_bound(start.evaluate(environment.clone())?)?;
+                let end = Self::range_bound(end.evaluate(environment.clone())?)?;
+
+                let step = match step {
+                    Some(step) => Self::range_bound(step.evaluate(environment)?)?,
+                    None => 1,
+                };
+
+                if step == 0 {
+                    return Err(String::from("Range step can't be 0").into());
+                }
+
+                Ok(RangeValue {
+                    start,
+                    end,
+                    step,
+                    inclusive: operator.token_type == DotDotEqual,
+                })
+            }
             Self::Call {
                 callee,
                 paren: _,
                 arguments,
+                named,
             } => {
                 let callable = (*callee).evaluate(environment.clone())?;
                 match callable {
-                    Callable { name, arity, fun } => {
-                        if arity != arguments.len() {
-                            return Err(format!(
-                                "Expected {} arguments but got {}.",
-                                arity,
-                                arguments.len()
-                            ));
-                        }
+                    Callable {
+                        ref fun,
+                        environment: ref closure,
+                        ..
+                    } => {
+                        let arguments =
+                            Self::call_arguments(&callable, arguments, named, environment)?;
 
-                        let mut parameters = vec![];
-                        for argument in arguments {
-                            let literal = argument.evaluate(environment.clone())?;
-                            parameters.push(literal);
-                        }
+                        fun(closure.clone(), &arguments)
+                    }
+                    LiteralValue::Class { .. } => {
+                        let instance = Instance {
+                            class: Box::new(callable.clone()),
+                            fields: Rc::new(RefCell::new(HashMap::new())),
+                        };
+
+                        match callable.find_method("init") {
+                            Some(initializer) => {
+                                // bound first, so that default values can use 'this'
+                                let initializer = initializer.bind(instance.clone())?;
+                                let arguments = Self::call_arguments(
+                                    &initializer,
+                                    arguments,
+                                    named,
+                                    environment,
+                                )?;
 
-                        // figure out if the variable can be the same name as the function??
-                        if let Err(_) = environment.borrow_mut().get(&name) {
-                            return Err(format!("undefined function {}", name));
+                                if let Callable {
+                                    fun,
+                                    environment: closure,
+                                    ..
+                                } = initializer
+                                {
+                                    fun(closure, &arguments)?;
+                                }
+                            }
+                            None => Self::check_arity(0, Some(0), arguments.len() + named.len())?,
                         }
 
-                        fun(environment, &parameters)
+                        Ok(instance)
+                    }
+                    _ => Err(format!("Cannot use {} as callable", callable.to_type()).into()),
+                }
+            }
+            Self::Get { object, name } => {
+                let object = object.evaluate(environment)?;
+                Ok(object.get_property(&name.lexeme)?)
+            }
+            Self::Set {
+                object,
+                name,
+                value,
+                operator,
+            } => {
+                let object = object.evaluate(environment.clone())?;
+                let mut value = value.evaluate(environment)?;
+
+                if let Some(operator) = operator {
+                    let current = object.get_property(&name.lexeme)?;
+                    value = Self::binary_operation(current, operator, value)?;
+                }
+
+                object.set_property(&name.lexeme, value.clone())?;
+                Ok(value)
+            }
+            Self::List { elements } => {
+                let mut values = vec![];
+                for element in elements {
+                    values.push(element.evaluate(environment.clone())?);
+                }
+
+                Ok(ListValue(Rc::new(RefCell::new(values))))
+            }
+            Self::Map { entries } => {
+                let mut values = HashMap::new();
+                for (key, value) in entries {
+                    let key = key.evaluate(environment.clone())?;
+                    let value = value.evaluate(environment.clone())?;
+                    values.insert(MapKey::try_from(&key)?, value);
+                }
+
+                Ok(MapValue(Rc::new(RefCell::new(values))))
+            }
+            Self::Index { object, index, .. } => {
+                let object = object.evaluate(environment.clone())?;
+                let index = index.evaluate(environment)?;
+                Ok(object.get_index(&index)?)
+            }
+            Self::SetIndex {
+                object,
+                index,
+                value,
+                operator,
+                ..
+            } => {
+                let object = object.evaluate(environment.clone())?;
+                let index = index.evaluate(environment.clone())?;
+                let mut value = value.evaluate(environment)?;
+
+                if let Some(operator) = operator {
+                    let current = object.get_index(&index)?;
+                    value = Self::binary_operation(current, operator, value)?;
+                }
+
+                object.set_index(&index, value.clone())?;
+                Ok(value)
+            }
+            Self::Postfix { target, operator } => {
+                let step = Token::new(
+                    if operator.token_type == PlusPlus {
+                        Plus
+                    } else {
+                        Minus
+                    },
+                    operator.lexeme[..1].to_string(),
+                    None,
+                    operator.line,
+                );
+
+                // the target is evaluated once, the value from before the update is returned
+                match target.as_ref() {
+                    Self::Variable { token, depth } => {
+                        let old =
+                            Self::look_up_variable(environment.clone(), &token.lexeme, depth)?;
+                        let new = Self::binary_operation(old.clone(), &step, IntValue(1))?;
+                        Self::assign_variable(environment, &token.lexeme, depth, new)?;
+                        Ok(old)
+                    }
+                    Self::Get { object, name } => {
+                        let object = object.evaluate(environment)?;
+                        let old = object.get_property(&name.lexeme)?;
+                        let new = Self::binary_operation(old.clone(), &step, IntValue(1))?;
+                        object.set_property(&name.lexeme, new)?;
+                        Ok(old)
+                    }
+                    Self::Index { object, index, .. } => {
+                        let object = object.evaluate(environment.clone())?;
+                        let index = index.evaluate(environment)?;
+                        let old = object.get_index(&index)?;
+                        let new = Self::binary_operation(old.clone(), &step, IntValue(1))?;
+                        object.set_index(&index, new)?;
+                        Ok(old)
                     }
-                    _ => Err(format!("Cannot use {} as callable", callable.to_type())),
+                    _ => Err(format!("Invalid {} target {}", operator.lexeme, target).into()),
+                }
+            }
+            Self::This { keyword, depth } => {
+                Ok(Self::look_up_variable(environment, &keyword.lexeme, depth)?)
+            }
+            Self::Super {
+                keyword,
+                method,
+                depth,
+            } => {
+                let superclass =
+                    Self::look_up_variable(environment.clone(), &keyword.lexeme, depth)?;
+                // "this" is always bound one scope inside the one holding "super"
+                let this_depth = depth.map(|distance| distance - 1);
+                let instance = Self::look_up_variable(environment, "this", &this_depth)?;
+
+                match superclass.find_method(&method.lexeme) {
+                    Some(found) => Ok(found.bind(instance)?),
+                    None => Err(format!("Undefined property {}", method.lexeme).into()),
+                }
+            }
+            Self::Lambda {
+                keyword: _,
+                parameters,
+                body,
+                ..
+            } => Ok(Interpreter::create_function(
+                String::from("anonymous"),
+                parameters.clone(),
+                body.clone(),
+                environment,
+            )),
+        }
+    }
+
+    pub fn call_arguments(
+        callable: &LiteralValue,
+        arguments: &[Expression],
+        named: &[(Token, Expression)],
+        environment: Rc<RefCell<Environment>>,
+    ) -> Result<Vec<LiteralValue>, RuntimeError> {
+        let (name, arity, max_arity, parameters, closure) = match callable {
+            Callable {
+                name,
+                arity,
+                max_arity,
+                parameters,
+                environment,
+                ..
+            } => (name, *arity, *max_arity, parameters, environment),
+            _ => return Err(format!("Cannot use {} as callable", callable.to_type()).into()),
+        };
+
+        if named.is_empty() {
+            Self::check_arity(arity, max_arity, arguments.len())?;
+        }
+
+        let mut values = vec![];
+        for argument in arguments {
+            values.push(argument.evaluate(environment.clone())?);
+        }
+
+        if named.is_empty() {
+            return Ok(values);
+        }
+
+        // every parameter before the rest one gets a slot, extra positional values go to rest
+        let fixed = parameters
+            .iter()
+            .take_while(|parameter| !parameter.rest)
+            .count();
+        let extra = values.split_off(values.len().min(fixed));
+
+        let mut slots: Vec<Option<LiteralValue>> = vec![None; fixed];
+        for (slot, value) in slots.iter_mut().zip(values) {
+            *slot = Some(value);
+        }
+
+        for (argument, value) in named {
+            let position = parameters
+                .iter()
+                .position(|parameter| parameter.name.lexeme == argument.lexeme);
+
+            match position {
+                Some(position) if parameters[position].rest => {
+                    return Err(format!(
+                        "Rest parameter {} of {} can't be passed by name",
+                        argument.lexeme, name
+                    )
+                    .into())
+                }
+                Some(position) if slots[position].is_some() => {
+                    return Err(
+                        format!("Duplicate argument {} for {}", argument.lexeme, name).into(),
+                    )
+                }
+                Some(position) => slots[position] = Some(value.evaluate(environment.clone())?),
+                None => {
+                    return Err(format!("Unknown argument {} for {}", argument.lexeme, name).into())
                 }
             }
         }
+
+        // defaults see the parameters before them, the same as when they are filled in by the call
+        let defaults = Rc::new(RefCell::new(Environment::new()));
+        defaults.borrow_mut().enclosing = Some(closure.clone());
+
+        let mut values = vec![];
+        for (parameter, slot) in parameters.iter().zip(slots) {
+            let value = match (slot, &parameter.default) {
+                (Some(value), _) => value,
+                (None, Some(default)) => default.evaluate(defaults.clone())?,
+                (None, None) => {
+                    return Err(
+                        format!("Missing argument {} for {}", parameter.name.lexeme, name).into(),
+                    )
+                }
+            };
+
+            defaults
+                .borrow_mut()
+                .define(String::from(&parameter.name.lexeme), value.clone())?;
+            values.push(value);
+        }
+
+        values.extend(extra);
+
+        Self::check_arity(arity, max_arity, values.len())?;
+
+        Ok(values)
+    }
+
+    // max_arity is None when a rest parameter takes any number of extra arguments
+    fn check_arity(arity: usize, max_arity: Option<usize>, count: usize) -> Result<(), String> {
+        match max_arity {
+            Some(max_arity) if arity == max_arity && count != arity => {
+                Err(format!("Expected {} arguments but got {}.", arity, count))
+            }
+            Some(max_arity) if count < arity || count > max_arity => Err(format!(
+                "Expected {} to {} arguments but got {}.",
+                arity, max_arity, count
+            )),
+            None if count < arity => Err(format!(
+                "Expected at least {} arguments but got {}.",
+                arity, count
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn range_bound(value: LiteralValue) -> Result<i64, String> {
+        match value {
+            IntValue(integer) => Ok(integer),
+            _ => Err(format!("Range bounds must be Int, got {}", value.to_type())),
+        }
+    }
+
+    fn line(&self) -> Option<usize> {
+        match self {
+            Self::Binary { operator, .. }
+            | Self::Unary { operator, .. }
+            | Self::Logical { operator, .. }
+            | Self::Range { operator, .. }
+            | Self::Postfix { operator, .. } => Some(operator.line),
+            Self::Variable { token, .. } => Some(token.line),
+            Self::Assign { name, .. } => Some(name.line),
+            Self::Call { paren, .. } => Some(paren.line),
+            Self::Get { name, .. } | Self::Set { name, .. } => Some(name.line),
+            Self::Index { bracket, .. } | Self::SetIndex { bracket, .. } => Some(bracket.line),
+            Self::This { keyword, .. }
+            | Self::Super { keyword, .. }
+            | Self::Lambda { keyword, .. } => Some(keyword.line),
+            Self::Literal { .. }
+            | Self::Grouping { .. }
+            | Self::Conditional { .. }
+            | Self::List { .. }
+            | Self::Map { .. } => None,
+        }
+    }
+
+    fn assign_variable(
+        environment: Rc<RefCell<Environment>>,
+        name: &str,
+        depth: &Option<usize>,
+        value: LiteralValue,
+    ) -> Result<(), String> {
+        match depth {
+            Some(distance) => {
+                environment
+                    .borrow_mut()
+                    .assign_at(*distance, String::from(name), value)
+            }
+            None => environment.borrow_mut().assign(String::from(name), value), // temp fix
+        }
+    }
+
+    fn compound_suffix(operator: &Option<Token>) -> String {
+        match operator {
+            Some(operator) => format!("{}=", operator.lexeme),
+            None => String::new(),
+        }
+    }
+
+    fn binary_operation(
+        left: LiteralValue,
+        operator: &Token,
+        right: LiteralValue,
+    ) -> Result<LiteralValue, String> {
+        match &operator.token_type {
+            Plus => left + right,
+            Minus => left - right,
+            Star => left * right,
+            Slash => left / right,
+            Percent => left % right,
+            SlashSlash => left.floor_divide(right),
+            StarStar => left.power(right),
+            Ampersand => left & right,
+            Pipe => left | right,
+            Caret => left ^ right,
+            LessLess => left << right,
+            GreaterGreater => left >> right,
+            Greater => compare_values!(>, left, right),
+            GreaterEqual => compare_values!(>=, left, right),
+            Less => compare_values!(<, left, right),
+            LessEqual => compare_values!(<=, left, right),
+            BangEqual => Ok(LiteralValue::from(!left.is_equal(&right))),
+            EqualEqual => Ok(LiteralValue::from(left.is_equal(&right))),
+            _ => {
+                LiteralValue::not_implemented_error(&stringify!(operator.token_type), &left, &right)
+            }
+        }
+    }
+
+    fn look_up_variable(
+        environment: Rc<RefCell<Environment>>,
+        name: &str,
+        depth: &Option<usize>,
+    ) -> Result<LiteralValue, String> {
+        match depth {
+            Some(distance) => environment.borrow().get_at(*distance, name),
+            None => environment.borrow().get(name),
+        }
     }
 }
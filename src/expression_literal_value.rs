@@ -1,10 +1,14 @@
 use crate::environment::Environment;
 use crate::expression_literal_value::LiteralValue::*;
+use crate::generator::Generator;
+use crate::interpreter::RuntimeError;
+use crate::statement::Parameter;
 use crate::token::{LiteralValue as TokenLiteralValue, Token, TokenType};
 
 use std::cell::RefCell;
+use std::collections::HashMap;
 use std::fmt::{self, Display, Formatter};
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Rem, Shl, Shr, Sub};
 use std::rc::Rc;
 use std::string::String;
 
@@ -20,15 +24,86 @@ pub enum LiteralValue {
     True,
     False,
     Nil,
+    ListValue(Rc<RefCell<Vec<LiteralValue>>>),
+    MapValue(Rc<RefCell<HashMap<MapKey, LiteralValue>>>),
     Callable {
         name: String,
         arity: usize,
+        max_arity: Option<usize>,
+        // empty for natives, those only take positional arguments
+        parameters: Rc<Vec<Parameter>>,
         fun: Rc<
-            dyn Fn(Rc<RefCell<Environment>>, &Vec<LiteralValue>) -> Result<LiteralValue, String>,
+            dyn Fn(
+                Rc<RefCell<Environment>>,
+                &Vec<LiteralValue>,
+            ) -> Result<LiteralValue, RuntimeError>,
         >,
+        environment: Rc<RefCell<Environment>>,
+    },
+    Class {
+        name: String,
+        methods: Rc<HashMap<String, LiteralValue>>,
+        superclass: Option<Box<LiteralValue>>,
+    },
+    Instance {
+        class: Box<LiteralValue>,
+        fields: Rc<RefCell<HashMap<String, LiteralValue>>>,
+    },
+    ErrorValue {
+        message: String,
+        line: usize,
+    },
+    Module {
+        name: String,
+        environment: Rc<RefCell<Environment>>,
+    },
+    Generator(Rc<RefCell<Generator>>),
+    // numbers are only worked out while iterating, so large ranges cost nothing
+    RangeValue {
+        start: i64,
+        end: i64,
+        step: i64,
+        inclusive: bool,
     },
 }
 
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum MapKey {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    String(String),
+}
+
+impl TryFrom<&LiteralValue> for MapKey {
+    type Error = String;
+
+    fn try_from(value: &LiteralValue) -> Result<Self, Self::Error> {
+        match value {
+            Nil => Ok(MapKey::Nil),
+            True => Ok(MapKey::Bool(true)),
+            False => Ok(MapKey::Bool(false)),
+            IntValue(integer) => Ok(MapKey::Int(*integer)),
+            StringValue(string) => Ok(MapKey::String(string.clone())),
+            _ => Err(format!(
+                "Map key must be String, Int, Bool or Nil, got {}",
+                value.to_type()
+            )),
+        }
+    }
+}
+
+impl From<MapKey> for LiteralValue {
+    fn from(key: MapKey) -> Self {
+        match key {
+            MapKey::Nil => Nil,
+            MapKey::Bool(boolean) => LiteralValue::from(boolean),
+            MapKey::Int(integer) => IntValue(integer),
+            MapKey::String(string) => StringValue(string),
+        }
+    }
+}
+
 impl From<Token> for LiteralValue {
     fn from(token: Token) -> Self {
         match token.token_type {
@@ -37,7 +112,7 @@ impl From<Token> for LiteralValue {
                 Some(TokenLiteralValue::FValue(float_value)) => FValue(float_value),
                 _ => panic!("Could not unwrap as number"),
             },
-            TokenType::String => match token.literal {
+            TokenType::String | TokenType::Interpolation => match token.literal {
                 Some(TokenLiteralValue::StringValue(string_value)) => StringValue(string_value),
                 _ => panic!("Could not unwrap as String"),
             },
@@ -74,7 +149,15 @@ impl From<&LiteralValue> for bool {
             True => true,
             False => false,
             Nil => false,
-            Callable { .. } => todo!(),
+            ListValue(elements) => !elements.borrow().is_empty(),
+            MapValue(entries) => !entries.borrow().is_empty(),
+            RangeValue { .. } => literal_value.range_numbers().next().is_some(),
+            Callable { .. }
+            | Class { .. }
+            | Instance { .. }
+            | ErrorValue { .. }
+            | Module { .. }
+            | Generator(_) => true,
         }
     }
 }
@@ -94,11 +177,14 @@ impl Display for LiteralValue {
             True => String::from("true"),
             False => String::from("false"),
             Nil => String::from("nil"),
-            Callable {
-                name,
-                arity,
-                fun: _,
-            } => format!("Callable: {} {}", name, arity),
+            ListValue(_) | MapValue(_) => format!("{:?}", self),
+            Callable { name, arity, .. } => format!("Callable: {} {}", name, arity),
+            Class { name, .. } => name.clone(),
+            Instance { class, .. } => format!("{} instance", class),
+            ErrorValue { message, line } => format!("{} at line {}", message, line),
+            Module { name, .. } => format!("{} module", name),
+            Generator(generator) => format!("{} generator", generator.borrow().name),
+            RangeValue { .. } => format!("{:?}", self),
         };
         write!(f, "{}", str)
     }
@@ -113,18 +199,69 @@ impl PartialEq for LiteralValue {
             (True, True) => true,
             (False, False) => true,
             (Nil, Nil) => true,
+            (ListValue(a), ListValue(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+            (MapValue(a), MapValue(b)) => Rc::ptr_eq(a, b) || *a.borrow() == *b.borrow(),
+            (Callable { fun: a_fun, .. }, Callable { fun: b_fun, .. }) => Rc::ptr_eq(a_fun, b_fun),
             (
-                Callable {
+                Class {
                     name: a_name,
-                    arity: a_arity,
-                    fun: _,
+                    methods: a_methods,
+                    ..
                 },
-                Callable {
+                Class {
                     name: b_name,
-                    arity: b_arity,
-                    fun: _,
+                    methods: b_methods,
+                    ..
+                },
+            ) => a_name == b_name && Rc::ptr_eq(a_methods, b_methods),
+            (
+                Instance {
+                    fields: a_fields, ..
+                },
+                Instance {
+                    fields: b_fields, ..
+                },
+            ) => Rc::ptr_eq(a_fields, b_fields),
+            (
+                ErrorValue {
+                    message: a_message,
+                    line: a_line,
                 },
-            ) => a_name == b_name && a_arity == b_arity,
+                ErrorValue {
+                    message: b_message,
+                    line: b_line,
+                },
+            ) => a_message == b_message && a_line == b_line,
+            (
+                Module {
+                    environment: a_environment,
+                    ..
+                },
+                Module {
+                    environment: b_environment,
+                    ..
+                },
+            ) => Rc::ptr_eq(a_environment, b_environment),
+            (Generator(a), Generator(b)) => Rc::ptr_eq(a, b),
+            (
+                RangeValue {
+                    start: a_start,
+                    end: a_end,
+                    step: a_step,
+                    inclusive: a_inclusive,
+                },
+                RangeValue {
+                    start: b_start,
+                    end: b_end,
+                    step: b_step,
+                    inclusive: b_inclusive,
+                },
+            ) => {
+                a_start == b_start
+                    && a_end == b_end
+                    && a_step == b_step
+                    && a_inclusive == b_inclusive
+            }
             _ => false,
         }
     }
@@ -139,9 +276,51 @@ impl fmt::Debug for LiteralValue {
             True => write!(f, "true"),
             False => write!(f, "false"),
             Nil => write!(f, "nil"),
+            ListValue(elements) => {
+                let comma_separated = elements
+                    .borrow()
+                    .iter()
+                    .map(|element| format!("{:?}", element))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                write!(f, "[{}]", comma_separated)
+            }
+            MapValue(_) => {
+                let comma_separated = self
+                    .map_entries()
+                    .iter()
+                    .map(|(key, value)| format!("{:?}: {:?}", key, value))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                write!(f, "{{{}}}", comma_separated)
+            }
             Callable { name, arity, .. } => {
                 write!(f, "Callable {{ name: {}, arity: {} }}", name, arity)
             }
+            Class { name, .. } => write!(f, "Class {{ name: {} }}", name),
+            Instance { class, .. } => write!(f, "Instance {{ class: {} }}", class),
+            ErrorValue { message, line } => {
+                write!(f, "Error {{ message: {:?}, line: {} }}", message, line)
+            }
+            Module { name, .. } => write!(f, "Module {{ name: {} }}", name),
+            Generator(generator) => {
+                write!(f, "Generator {{ name: {} }}", generator.borrow().name)
+            }
+            RangeValue {
+                start,
+                end,
+                step,
+                inclusive,
+            } => {
+                let operator = if *inclusive { "..=" } else { ".." };
+
+                match step {
+                    1 => write!(f, "{}{}{}", start, operator, end),
+                    _ => write!(f, "{}{}{} step {}", start, operator, end, step),
+                }
+            }
         }
     }
 }
@@ -154,10 +333,291 @@ impl LiteralValue {
             True | False => "Bool",
             StringValue(_) => "String",
             Nil => "Nil",
+            ListValue(_) => "List",
+            MapValue(_) => "Map",
             Callable { .. } => "Callable",
+            Class { .. } => "Class",
+            Instance { .. } => "Instance",
+            ErrorValue { .. } => "Error",
+            Module { .. } => "Module",
+            Generator(_) => "Generator",
+            RangeValue { .. } => "Range",
+        }
+    }
+
+    // equality across all types, numbers compare by value regardless of Int or Float
+    pub fn is_equal(&self, other: &LiteralValue) -> bool {
+        match (self, other) {
+            (IntValue(x), FValue(y)) | (FValue(y), IntValue(x)) => {
+                (*x as f64 - y).abs() < f64::EPSILON
+            }
+            _ => self == other,
+        }
+    }
+
+    pub fn to_number(&self) -> Option<f64> {
+        match self {
+            IntValue(integer) => Some(*integer as f64),
+            FValue(float) => Some(*float),
+            _ => None,
+        }
+    }
+
+    pub fn find_method(&self, name: &str) -> Option<LiteralValue> {
+        match self {
+            Class {
+                methods,
+                superclass,
+                ..
+            } => match methods.get(name) {
+                Some(method) => Some(method.clone()),
+                None => superclass
+                    .as_ref()
+                    .and_then(|superclass| superclass.find_method(name)),
+            },
+            _ => None,
+        }
+    }
+
+    pub fn bind(&self, instance: LiteralValue) -> Result<LiteralValue, String> {
+        match self {
+            Callable {
+                name,
+                arity,
+                max_arity,
+                parameters,
+                fun,
+                environment,
+            } => {
+                let mut bound_environment = Environment::new();
+                bound_environment.enclosing = Some(environment.clone());
+                bound_environment.define(String::from("this"), instance)?;
+
+                Ok(Callable {
+                    name: name.clone(),
+                    arity: *arity,
+                    max_arity: *max_arity,
+                    parameters: parameters.clone(),
+                    fun: fun.clone(),
+                    environment: Rc::new(RefCell::new(bound_environment)),
+                })
+            }
+            _ => Err(format!("Cannot bind {} as method", self.to_type())),
+        }
+    }
+
+    pub fn get_property(&self, name: &str) -> Result<LiteralValue, String> {
+        match self {
+            Instance { class, fields } => {
+                if let Some(value) = fields.borrow().get(name) {
+                    return Ok(value.clone());
+                }
+
+                match class.find_method(name) {
+                    Some(method) => method.bind(self.clone()),
+                    None => Err(format!("Undefined property {}", name)),
+                }
+            }
+            ErrorValue { message, line } => match name {
+                "message" => Ok(StringValue(message.clone())),
+                "line" => Ok(IntValue(*line as i64)),
+                _ => Err(format!("Undefined property {}", name)),
+            },
+            Module {
+                name: module,
+                environment,
+            } => match environment.borrow().values.get(name) {
+                Some(value) => Ok(value.clone()),
+                None => Err(format!("Undefined member {} of module {}", name, module)),
+            },
+            Generator(generator) => Self::generator_method(generator, name),
+            _ => Err(format!(
+                "Only instances have properties, got {}",
+                self.to_type()
+            )),
+        }
+    }
+
+    // next() resumes the generator until its following yield, has_next() looks ahead without losing it
+    fn generator_method(
+        generator: &Rc<RefCell<Generator>>,
+        name: &str,
+    ) -> Result<LiteralValue, String> {
+        if name != "next" && name != "has_next" {
+            return Err(format!("Undefined property {}", name));
+        }
+
+        let generator = generator.clone();
+        let looks_ahead = name == "has_next";
+
+        let fun = move |_, _: &Vec<LiteralValue>| match generator.try_borrow_mut() {
+            Ok(mut generator) if looks_ahead => generator.has_next().map(LiteralValue::from),
+            Ok(mut generator) => generator.next(),
+            Err(_) => Err(String::from("Generator is already running").into()),
+        };
+
+        Ok(Callable {
+            name: String::from(name),
+            arity: 0,
+            max_arity: Some(0),
+            parameters: Rc::new(vec![]),
+            fun: Rc::new(fun),
+            environment: Rc::new(RefCell::new(Environment::new())),
+        })
+    }
+
+    pub fn set_property(&self, name: &str, value: LiteralValue) -> Result<(), String> {
+        match self {
+            Instance { fields, .. } => {
+                fields.borrow_mut().insert(String::from(name), value);
+                Ok(())
+            }
+            _ => Err(format!(
+                "Only instances have fields, got {}",
+                self.to_type()
+            )),
         }
     }
 
+    pub fn get_index(&self, index: &LiteralValue) -> Result<LiteralValue, String> {
+        match (self, index) {
+            (ListValue(elements), RangeValue { .. }) => {
+                let elements = elements.borrow();
+                let slice = Self::slice(&elements, index)?;
+                Ok(ListValue(Rc::new(RefCell::new(slice))))
+            }
+            (StringValue(string), RangeValue { .. }) => {
+                let characters = string
+                    .chars()
+                    .map(|character| StringValue(character.to_string()))
+                    .collect::<Vec<LiteralValue>>();
+
+                let slice = Self::slice(&characters, index)?;
+                Ok(StringValue(slice.iter().map(|c| c.to_string()).collect()))
+            }
+            (ListValue(elements), _) => {
+                let elements = elements.borrow();
+                let position = Self::list_position(index, elements.len())?;
+                Ok(elements[position].clone())
+            }
+            (MapValue(entries), _) => {
+                let key = MapKey::try_from(index)?;
+
+                match entries.borrow().get(&key) {
+                    Some(value) => Ok(value.clone()),
+                    None => Err(format!("Undefined key {:?}", index)),
+                }
+            }
+            _ => Err(format!("Cannot index into {}", self.to_type())),
+        }
+    }
+
+    pub fn set_index(&self, index: &LiteralValue, value: LiteralValue) -> Result<(), String> {
+        match self {
+            ListValue(elements) => {
+                let mut elements = elements.borrow_mut();
+                let position = Self::list_position(index, elements.len())?;
+                elements[position] = value;
+                Ok(())
+            }
+            MapValue(entries) => {
+                entries.borrow_mut().insert(MapKey::try_from(index)?, value);
+                Ok(())
+            }
+            _ => Err(format!("Cannot assign index of {}", self.to_type())),
+        }
+    }
+
+    // entries are sorted by key so that printing and iteration are stable
+    pub fn map_entries(&self) -> Vec<(LiteralValue, LiteralValue)> {
+        match self {
+            MapValue(entries) => {
+                let mut entries = entries
+                    .borrow()
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone()))
+                    .collect::<Vec<(MapKey, LiteralValue)>>();
+                entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+                entries
+                    .into_iter()
+                    .map(|(key, value)| (LiteralValue::from(key), value))
+                    .collect()
+            }
+            _ => vec![],
+        }
+    }
+
+    // picks the items at each index of a range, in the order the range walks them
+    fn slice(items: &[LiteralValue], range: &LiteralValue) -> Result<Vec<LiteralValue>, String> {
+        range
+            .range_numbers()
+            .map(|index| {
+                let position = Self::list_position(&IntValue(index), items.len())?;
+                Ok(items[position].clone())
+            })
+            .collect()
+    }
+
+    pub fn range_contains(&self, number: i64) -> bool {
+        match self {
+            RangeValue {
+                start,
+                end,
+                step,
+                inclusive,
+            } => {
+                let within = if *step > 0 {
+                    number >= *start && (number < *end || *inclusive && number == *end)
+                } else {
+                    number <= *start && (number > *end || *inclusive && number == *end)
+                };
+
+                within && (number as i128 - *start as i128) % *step as i128 == 0
+            }
+            _ => false,
+        }
+    }
+
+    pub fn range_numbers(&self) -> Box<dyn Iterator<Item = i64>> {
+        let range = self.clone();
+
+        match self {
+            RangeValue { start, step, .. } => {
+                let step = *step;
+
+                Box::new(
+                    std::iter::successors(Some(*start), move |number| number.checked_add(step))
+                        .take_while(move |number| range.range_contains(*number)),
+                )
+            }
+            _ => Box::new(std::iter::empty()),
+        }
+    }
+
+    // negative indices count from the end of the list
+    pub fn list_position(index: &LiteralValue, length: usize) -> Result<usize, String> {
+        let index = match index {
+            IntValue(index) => *index,
+            _ => return Err(format!("List index must be Int, got {}", index.to_type())),
+        };
+
+        let position = if index < 0 {
+            index + length as i64
+        } else {
+            index
+        };
+
+        if position < 0 || position >= length as i64 {
+            return Err(format!(
+                "Index {} out of range for list of length {}",
+                index, length
+            ));
+        }
+
+        Ok(position as usize)
+    }
+
     pub fn not_implemented_error(
         token_type: &str,
         left: &LiteralValue,
@@ -188,28 +648,120 @@ macro_rules! compare_values {
 
 macro_rules! arithmetic_operation {
     ($left: expr, $op_symbol:tt, $right: expr) => {
+        arithmetic_operation!($left, stringify!($op_symbol), $right, |x, y| x $op_symbol y)
+    };
+    ($left: expr, $op_name: expr, $right: expr, |$x: ident, $y: ident| $operation: expr) => {
         {
-            if stringify!($op_symbol) == "/" && matches!($right, IntValue(0) | FValue(0.0)) {
+            if matches!($op_name, "/" | "%" | "//") && matches!($right, IntValue(0) | FValue(0.0)) {
                 return Err(String::from("Division by 0"));
             }
 
             match (&$left, &$right) {
-                (IntValue(x), IntValue(y)) => Ok(IntValue(x $op_symbol y)),
-                (FValue(x), FValue(y)) => Ok(FValue(x $op_symbol y)),
-                (IntValue(x), FValue(y)) => Ok(FValue((*x as f64) $op_symbol y)),
-                (FValue(x), IntValue(y)) => Ok(FValue(x $op_symbol (*y as f64))),
-                (StringValue(string), any) if stringify!($op_symbol) == "+" => {
+                (IntValue(x), IntValue(y)) => {
+                    let ($x, $y) = (*x, *y);
+                    Ok(IntValue($operation))
+                }
+                (FValue(x), FValue(y)) => {
+                    let ($x, $y) = (*x, *y);
+                    Ok(FValue($operation))
+                }
+                (IntValue(x), FValue(y)) => {
+                    let ($x, $y) = (*x as f64, *y);
+                    Ok(FValue($operation))
+                }
+                (FValue(x), IntValue(y)) => {
+                    let ($x, $y) = (*x, *y as f64);
+                    Ok(FValue($operation))
+                }
+                (StringValue(string), any) if $op_name == "+" => {
                     Ok(StringValue(format!("{0}{1}", string, any)))
                 }
-                (any, StringValue(string)) if stringify!($op_symbol) == "+" => {
+                (any, StringValue(string)) if $op_name == "+" => {
                     Ok(StringValue(format!("{0}{1}", any, string)))
                 }
-                _ => LiteralValue::not_implemented_error(&stringify!($op_symbol), &$left, &$right),
+                _ => LiteralValue::not_implemented_error($op_name, &$left, &$right),
             }
         }
     };
 }
 
+// floored like python, so that x == (x // y) * y + x % y holds for negative operands too
+trait NumericOperations {
+    fn floor_divide(self, rhs: Self) -> Self;
+    fn modulo(self, rhs: Self) -> Self;
+    fn power(self, rhs: Self) -> Self;
+}
+
+impl NumericOperations for i64 {
+    fn floor_divide(self, rhs: Self) -> Self {
+        let quotient = self / rhs;
+
+        if self % rhs != 0 && (self < 0) != (rhs < 0) {
+            quotient - 1
+        } else {
+            quotient
+        }
+    }
+
+    fn modulo(self, rhs: Self) -> Self {
+        let remainder = self % rhs;
+
+        if remainder != 0 && (remainder < 0) != (rhs < 0) {
+            remainder + rhs
+        } else {
+            remainder
+        }
+    }
+
+    fn power(self, rhs: Self) -> Self {
+        self.pow(rhs as u32)
+    }
+}
+
+impl NumericOperations for f64 {
+    fn floor_divide(self, rhs: Self) -> Self {
+        (self / rhs).floor()
+    }
+
+    fn modulo(self, rhs: Self) -> Self {
+        let remainder = self % rhs;
+
+        if remainder != 0.0 && (remainder < 0.0) != (rhs < 0.0) {
+            remainder + rhs
+        } else {
+            remainder
+        }
+    }
+
+    fn power(self, rhs: Self) -> Self {
+        self.powf(rhs)
+    }
+}
+
+impl LiteralValue {
+    pub fn floor_divide(self, rhs: Self) -> Result<Self, String> {
+        match (&self, &rhs) {
+            (IntValue(x), IntValue(y)) if *y != 0 && x.checked_div(*y).is_none() => {
+                Err(String::from("Integer overflow in //"))
+            }
+            _ => arithmetic_operation!(&self, "//", &rhs, |x, y| x.floor_divide(y)),
+        }
+    }
+
+    pub fn power(self, rhs: Self) -> Result<Self, String> {
+        match (&self, &rhs) {
+            // a negative exponent can't give an integer back
+            (IntValue(x), IntValue(y)) if *y < 0 => Ok(FValue((*x as f64).powf(*y as f64))),
+            (IntValue(x), IntValue(y)) => u32::try_from(*y)
+                .ok()
+                .and_then(|exponent| x.checked_pow(exponent))
+                .map(IntValue)
+                .ok_or(String::from("Integer overflow in **")),
+            _ => arithmetic_operation!(&self, "**", &rhs, |x, y| x.power(y)),
+        }
+    }
+}
+
 impl Add<LiteralValue> for LiteralValue {
     type Output = Result<Self, String>;
 
@@ -241,3 +793,73 @@ impl Div<LiteralValue> for LiteralValue {
         arithmetic_operation!(&self, /, &_rhs)
     }
 }
+
+impl Rem<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn rem(self, _rhs: Self) -> Self::Output {
+        match (&self, &_rhs) {
+            (IntValue(x), IntValue(y)) if *y != 0 && x.checked_rem(*y).is_none() => {
+                Err(String::from("Integer overflow in %"))
+            }
+            _ => arithmetic_operation!(&self, "%", &_rhs, |x, y| x.modulo(y)),
+        }
+    }
+}
+
+macro_rules! bitwise_operation {
+    ($left: expr, $op_symbol:tt, $right: expr) => {
+        match (&$left, &$right) {
+            (IntValue(_), IntValue(y)) if matches!(stringify!($op_symbol), "<<" | ">>") && !(0..64).contains(y) => {
+                Err(format!("Shift amount must be between 0 and 63, got {}", y))
+            }
+            (IntValue(x), IntValue(y)) => Ok(IntValue(x $op_symbol y)),
+            _ => Err(format!(
+                "{:?} operation is only implemented for Int operands, got {} and {}",
+                stringify!($op_symbol).parse::<TokenType>()?,
+                $left.to_type(),
+                $right.to_type()
+            )),
+        }
+    };
+}
+
+impl BitAnd<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn bitand(self, _rhs: Self) -> Self::Output {
+        bitwise_operation!(self, &, _rhs)
+    }
+}
+
+impl BitOr<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn bitor(self, _rhs: Self) -> Self::Output {
+        bitwise_operation!(self, |, _rhs)
+    }
+}
+
+impl BitXor<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn bitxor(self, _rhs: Self) -> Self::Output {
+        bitwise_operation!(self, ^, _rhs)
+    }
+}
+
+impl Shl<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn shl(self, _rhs: Self) -> Self::Output {
+        bitwise_operation!(self, <<, _rhs)
+    }
+}
+
+impl Shr<LiteralValue> for LiteralValue {
+    type Output = Result<Self, String>;
+
+    fn shr(self, _rhs: Self) -> Self::Output {
+        bitwise_operation!(self, >>, _rhs)
+    }
+}
@@ -0,0 +1,432 @@
+use crate::environment::Environment;
+use crate::expression::Expression;
+use crate::expression_literal_value::LiteralValue;
+use crate::interpreter::{ControlFlow, Interpreter, RuntimeError};
+use crate::iterator::ValueIterator;
+use crate::statement::Statement;
+use crate::token::Token;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[cfg(test)]
+#[path = "./tests/generator_tests.rs"]
+mod tests;
+
+// a generator body can't be run by the recursive interpreter, as that can't stop half way through
+// a statement, so the statements holding a yield are walked with an explicit stack of frames
+// and everything else still goes through the interpreter
+enum Frame {
+    Statements {
+        statements: Vec<Statement>,
+        index: usize,
+        // environment to go back to once a block is left
+        enclosing: Option<Rc<RefCell<Environment>>>,
+    },
+    Loop {
+        condition: Box<Expression>,
+        body: Box<Statement>,
+        increment: Option<Box<Expression>>,
+        started: bool,
+    },
+    Iterate {
+        variable: String,
+        iterator: ValueIterator,
+        body: Box<Statement>,
+    },
+    // sits below the frames of the block it guards, so errors and early exits find it
+    Try {
+        keyword: Token,
+        catch: Option<(Token, Vec<Statement>)>,
+        finally: Option<Vec<Statement>>,
+        stage: TryStage,
+    },
+}
+
+enum TryStage {
+    Body,
+    Catch,
+    // the outcome of try and catch, carried on with once the finally block is done
+    Finally(Result<ControlFlow, RuntimeError>),
+}
+
+pub struct Generator {
+    pub name: String,
+    frames: Vec<Frame>,
+    environment: Rc<RefCell<Environment>>,
+    // value produced by has_next, handed out by the following next
+    buffered: Option<LiteralValue>,
+    done: bool,
+}
+
+impl Generator {
+    pub fn new(name: String, body: Vec<Statement>, environment: Rc<RefCell<Environment>>) -> Self {
+        Self {
+            name,
+            frames: vec![Frame::Statements {
+                statements: body,
+                index: 0,
+                enclosing: None,
+            }],
+            environment,
+            buffered: None,
+            done: false,
+        }
+    }
+
+    pub fn next(&mut self) -> Result<LiteralValue, RuntimeError> {
+        if let Some(value) = self.buffered.take() {
+            return Ok(value);
+        }
+
+        match self.resume()? {
+            Some(value) => Ok(value),
+            None => Err(format!("Generator {} is exhausted", self.name).into()),
+        }
+    }
+
+    pub fn has_next(&mut self) -> Result<bool, RuntimeError> {
+        if self.buffered.is_none() {
+            self.buffered = self.resume()?;
+        }
+
+        Ok(self.buffered.is_some())
+    }
+
+    fn resume(&mut self) -> Result<Option<LiteralValue>, RuntimeError> {
+        if self.done {
+            return Ok(None);
+        }
+
+        let result = self.run();
+
+        // finishing, returning and failing all end the generator for good
+        if !matches!(result, Ok(Some(_))) {
+            self.done = true;
+            self.frames.clear();
+        }
+
+        result
+    }
+
+    fn run(&mut self) -> Result<Option<LiteralValue>, RuntimeError> {
+        while !self.frames.is_empty() {
+            match self.advance() {
+                Ok(Some(value)) => return Ok(Some(value)),
+                Ok(None) => {}
+                Err(error) => self.raise(error)?,
+            }
+        }
+
+        Ok(None)
+    }
+
+    // does one piece of work on the innermost frame, handing out a value when that was a yield
+    fn advance(&mut self) -> Result<Option<LiteralValue>, RuntimeError> {
+        let statement = match self.frames.last_mut() {
+            None => return Ok(None),
+            Some(Frame::Statements {
+                statements, index, ..
+            }) => match statements.get(*index) {
+                Some(statement) => {
+                    *index += 1;
+                    statement.clone()
+                }
+                None => {
+                    self.pop_frame();
+                    return Ok(None);
+                }
+            },
+            Some(Frame::Loop {
+                condition,
+                body,
+                increment,
+                started,
+            }) => {
+                if *started {
+                    if let Some(increment) = increment {
+                        increment.evaluate(self.environment.clone())?;
+                    }
+                }
+                *started = true;
+
+                if bool::from(condition.evaluate(self.environment.clone())?) {
+                    let body = *body.clone();
+                    self.frames.push(Frame::Statements {
+                        statements: vec![body],
+                        index: 0,
+                        enclosing: None,
+                    });
+                } else {
+                    self.pop_frame();
+                }
+
+                return Ok(None);
+            }
+            Some(Frame::Iterate {
+                variable,
+                iterator,
+                body,
+            }) => {
+                match iterator.next()? {
+                    Some(value) => {
+                        let mut environment = Environment::new();
+                        environment.enclosing = Some(self.environment.clone());
+                        environment.define(variable.clone(), value)?;
+
+                        let statements = vec![*body.clone()];
+                        let enclosing = self.environment.clone();
+                        self.environment = Rc::new(RefCell::new(environment));
+
+                        self.frames.push(Frame::Statements {
+                            statements,
+                            index: 0,
+                            enclosing: Some(enclosing),
+                        });
+                    }
+                    None => self.pop_frame(),
+                }
+
+                return Ok(None);
+            }
+            Some(Frame::Try { .. }) => {
+                if let Some(Frame::Try {
+                    keyword,
+                    finally,
+                    stage,
+                    ..
+                }) = self.frames.pop()
+                {
+                    match stage {
+                        TryStage::Finally(outcome) => self.unwind(outcome?),
+                        TryStage::Body | TryStage::Catch => {
+                            if let Some(finally) = finally {
+                                self.enter_finally(keyword, finally, Ok(ControlFlow::Normal));
+                            }
+                        }
+                    }
+                }
+
+                return Ok(None);
+            }
+        };
+
+        self.step(statement)
+    }
+
+    fn step(&mut self, statement: Statement) -> Result<Option<LiteralValue>, RuntimeError> {
+        if !statement.contains_yield() {
+            let mut interpreter = Interpreter::for_environment(self.environment.clone());
+            let flow = interpreter.execute_statements(vec![statement])?;
+            self.unwind(flow);
+
+            return Ok(None);
+        }
+
+        match statement {
+            Statement::Yield { value, .. } => match value {
+                Some(value) => Ok(Some(value.evaluate(self.environment.clone())?)),
+                None => Ok(Some(LiteralValue::Nil)),
+            },
+            Statement::Block { statements } => {
+                self.push_block(statements, Environment::new());
+                Ok(None)
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                let branch = if bool::from(condition.evaluate(self.environment.clone())?) {
+                    Some(*then_branch)
+                } else {
+                    else_branch.map(|else_branch| *else_branch)
+                };
+
+                if let Some(branch) = branch {
+                    self.frames.push(Frame::Statements {
+                        statements: vec![branch],
+                        index: 0,
+                        enclosing: None,
+                    });
+                }
+                Ok(None)
+            }
+            Statement::While {
+                condition,
+                body,
+                increment,
+            } => {
+                self.frames.push(Frame::Loop {
+                    condition: Box::new(condition),
+                    body,
+                    increment: increment.map(Box::new),
+                    started: false,
+                });
+                Ok(None)
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+            } => {
+                let iterable = iterable.evaluate(self.environment.clone())?;
+
+                self.frames.push(Frame::Iterate {
+                    variable: variable.lexeme,
+                    iterator: ValueIterator::new(iterable, variable.line)?,
+                    body,
+                });
+                Ok(None)
+            }
+            Statement::Match { subject, arms, .. } => {
+                let value = subject.evaluate(self.environment.clone())?;
+
+                let arm = arms
+                    .into_iter()
+                    .find(|(patterns, _)| patterns.iter().any(|pattern| pattern.matches(&value)));
+
+                if let Some((_, body)) = arm {
+                    self.frames.push(Frame::Statements {
+                        statements: vec![body],
+                        index: 0,
+                        enclosing: None,
+                    });
+                }
+                Ok(None)
+            }
+            Statement::Try {
+                keyword,
+                body,
+                catch,
+                finally,
+            } => {
+                self.frames.push(Frame::Try {
+                    keyword,
+                    catch,
+                    finally,
+                    stage: TryStage::Body,
+                });
+                self.push_block(body, Environment::new());
+                Ok(None)
+            }
+            _ => Err(format!("Generator {} can't yield from here", self.name).into()),
+        }
+    }
+
+    // leaves frames the way break, continue and return leave the statements they are in,
+    // stopping at a finally block on the way out that has to run first
+    fn unwind(&mut self, flow: ControlFlow) {
+        if flow == ControlFlow::Normal {
+            return;
+        }
+
+        while let Some(frame) = self.frames.pop() {
+            match frame {
+                Frame::Loop { .. } | Frame::Iterate { .. }
+                    if !matches!(flow, ControlFlow::Return(_)) =>
+                {
+                    if flow == ControlFlow::Continue {
+                        self.frames.push(frame);
+                    }
+                    break;
+                }
+                Frame::Try {
+                    keyword,
+                    finally: Some(finally),
+                    stage: TryStage::Body | TryStage::Catch,
+                    ..
+                } => {
+                    self.enter_finally(keyword, finally, Ok(flow));
+                    break;
+                }
+                Frame::Statements {
+                    enclosing: Some(enclosing),
+                    ..
+                } => self.environment = enclosing,
+                _ => {}
+            }
+        }
+    }
+
+    // hands an error to the innermost try frame, ending the generator when there is none
+    fn raise(&mut self, error: RuntimeError) -> Result<(), RuntimeError> {
+        while let Some(frame) = self.frames.pop() {
+            match frame {
+                Frame::Try {
+                    keyword,
+                    catch: Some((name, statements)),
+                    finally,
+                    stage: TryStage::Body,
+                } => {
+                    let line = keyword.line;
+                    self.frames.push(Frame::Try {
+                        keyword,
+                        catch: None,
+                        finally,
+                        stage: TryStage::Catch,
+                    });
+
+                    let mut environment = Environment::new();
+                    environment.define(name.lexeme, Interpreter::caught(error, line))?;
+                    self.push_block(statements, environment);
+                    return Ok(());
+                }
+                Frame::Try {
+                    keyword,
+                    finally: Some(finally),
+                    stage: TryStage::Body | TryStage::Catch,
+                    ..
+                } => {
+                    self.enter_finally(keyword, finally, Err(error));
+                    return Ok(());
+                }
+                Frame::Statements {
+                    enclosing: Some(enclosing),
+                    ..
+                } => self.environment = enclosing,
+                _ => {}
+            }
+        }
+
+        Err(error)
+    }
+
+    fn enter_finally(
+        &mut self,
+        keyword: Token,
+        finally: Vec<Statement>,
+        outcome: Result<ControlFlow, RuntimeError>,
+    ) {
+        self.frames.push(Frame::Try {
+            keyword,
+            catch: None,
+            finally: None,
+            stage: TryStage::Finally(outcome),
+        });
+        self.push_block(finally, Environment::new());
+    }
+
+    fn push_block(&mut self, statements: Vec<Statement>, mut environment: Environment) {
+        environment.enclosing = Some(self.environment.clone());
+
+        let enclosing = self.environment.clone();
+        self.environment = Rc::new(RefCell::new(environment));
+
+        self.frames.push(Frame::Statements {
+            statements,
+            index: 0,
+            enclosing: Some(enclosing),
+        });
+    }
+
+    fn pop_frame(&mut self) {
+        if let Some(Frame::Statements {
+            enclosing: Some(enclosing),
+            ..
+        }) = self.frames.pop()
+        {
+            self.environment = enclosing;
+        }
+    }
+}
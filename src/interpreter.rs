@@ -1,11 +1,20 @@
 use crate::environment::Environment;
 use crate::expression::Expression;
 use crate::expression_literal_value::LiteralValue;
-use crate::statement::Statement;
+use crate::generator::Generator;
+use crate::iterator::ValueIterator;
+use crate::natives::define_natives;
+use crate::parser::Parser;
+use crate::resolver::Resolver;
+use crate::scanner::Scanner;
+use crate::statement::{Parameter, Statement};
+use crate::typechecker::TypeChecker;
 
 use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
 use std::rc::Rc;
-use std::time::SystemTime;
 
 #[cfg(test)]
 #[path = "./tests/interpreter_tests.rs"]
@@ -13,50 +22,110 @@ mod tests;
 
 pub struct Interpreter {
     pub environment: Rc<RefCell<Environment>>,
-    specials: Rc<RefCell<Environment>>,
+    // file being run, imports are relative to it
+    path: Option<PathBuf>,
+    // imported modules by canonical path, None while the module is still running
+    modules: Rc<RefCell<HashMap<PathBuf, Option<LiteralValue>>>>,
 }
 
-fn clock_impl(
-    _env: Rc<RefCell<Environment>>,
-    _args: &Vec<LiteralValue>,
-) -> Result<LiteralValue, String> {
-    let now = SystemTime::now()
-        .duration_since(SystemTime::UNIX_EPOCH)
-        .expect("Could not get time")
-        .as_secs();
+// an error unwinding through running code, value is what a catch block receives
+#[derive(Debug, Clone, PartialEq)]
+pub struct RuntimeError {
+    pub message: String,
+    // the thrown value, or an error value once the line of the error is known
+    pub value: Option<LiteralValue>,
+}
+
+impl From<String> for RuntimeError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            value: None,
+        }
+    }
+}
 
-    Ok(LiteralValue::IntValue(now as i64))
+#[derive(Debug, PartialEq)]
+pub enum ControlFlow {
+    Normal,
+    Return(LiteralValue),
+    Break,
+    Continue,
 }
 
 impl Interpreter {
     pub fn new() -> Self {
         let mut environment = Environment::new();
-        environment.define(
-            String::from("clock"),
-            LiteralValue::Callable {
-                name: String::from("clock"),
-                arity: 0,
-                fun: Rc::new(clock_impl),
-            },
-        );
+        define_natives(&mut environment);
 
         Self {
-            specials: Rc::new(RefCell::new(Environment::new())),
             environment: Rc::new(RefCell::new(environment)),
+            path: None,
+            modules: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn for_file(path: &str) -> Self {
+        let mut interpreter = Self::new();
+        interpreter.path = Some(PathBuf::from(path));
+
+        // the entry file is running too, importing it back is circular
+        if let Ok(file) = fs::canonicalize(path) {
+            interpreter.modules.borrow_mut().insert(file, None);
         }
+
+        interpreter
     }
 
     fn for_closure(parent: Rc<RefCell<Environment>>) -> Self {
         let environment = Rc::new(RefCell::new(Environment::new()));
         environment.borrow_mut().enclosing = Some(parent.clone());
 
+        Self::for_environment(environment)
+    }
+
+    pub fn for_environment(environment: Rc<RefCell<Environment>>) -> Self {
         Self {
-            specials: Rc::new(RefCell::new(Environment::new())),
             environment,
+            path: None,
+            modules: Rc::new(RefCell::new(HashMap::new())),
+        }
+    }
+
+    pub fn run(&mut self, source: &str) -> Result<(), String> {
+        self.run_source(source).map_err(|error| error.message)
+    }
+
+    fn run_source(&mut self, source: &str) -> Result<(), RuntimeError> {
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens()?;
+
+        let mut parser = Parser::new(tokens);
+        let mut statements = parser.parse()?;
+
+        for warning in &parser.warnings {
+            eprintln!("Warning: {}", warning);
         }
+
+        let mut resolver = Resolver::new();
+        resolver.resolve(&mut statements)?;
+
+        let mut type_checker = TypeChecker::new();
+        type_checker.check(&statements)?;
+
+        self.interpret_statements(statements)
+    }
+
+    pub fn interpret_statements(&mut self, statements: Vec<Statement>) -> Result<(), RuntimeError> {
+        self.execute_statements(statements)?;
+
+        Ok(())
     }
 
-    pub fn interpret_statements(&mut self, statements: Vec<Statement>) -> Result<(), String> {
+    pub fn execute_statements(
+        &mut self,
+        statements: Vec<Statement>,
+    ) -> Result<ControlFlow, RuntimeError> {
         for statement in statements {
             match statement {
                 Statement::Expression { expression } => {
@@ -66,7 +135,9 @@ impl Interpreter {
                     let result = expression.evaluate(self.environment.clone())?;
                     println!("{}", result);
                 }
-                Statement::Variable { token, initializer } => {
+                Statement::Variable {
+                    token, initializer, ..
+                } => {
                     let value = match initializer {
                         Expression::Literal {
                             value: LiteralValue::Nil,
@@ -74,18 +145,23 @@ impl Interpreter {
                         _ => initializer.evaluate(self.environment.clone())?,
                     };
 
-                    self.environment.borrow_mut().define(token.lexeme, value);
+                    self.environment.borrow_mut().define(token.lexeme, value)?;
                 }
-                Statement::Block { statements } => {
-                    let mut new_environment = Environment::new();
-                    new_environment.enclosing = Some(self.environment.clone());
+                Statement::Const {
+                    token, initializer, ..
+                } => {
+                    let value = initializer.evaluate(self.environment.clone())?;
 
-                    let old_environment = self.environment.clone();
-                    self.environment = Rc::new(RefCell::new(new_environment));
-                    let block_result = self.interpret_statements(statements);
-                    self.environment = old_environment;
+                    self.environment
+                        .borrow_mut()
+                        .define_constant(token.lexeme, value)?;
+                }
+                Statement::Block { statements } => {
+                    let flow = self.execute_block(statements, Environment::new())?;
 
-                    block_result?
+                    if flow != ControlFlow::Normal {
+                        return Ok(flow);
+                    }
                 }
                 Statement::If {
                     condition,
@@ -94,56 +170,136 @@ impl Interpreter {
                 } => {
                     let condition_value = condition.evaluate(self.environment.clone())?;
 
-                    if bool::from(condition_value) {
-                        self.interpret_statements(vec![*then_branch])?;
+                    let flow = if bool::from(condition_value) {
+                        self.execute_statements(vec![*then_branch])?
                     } else if let Some(else_branch_value) = else_branch {
-                        self.interpret_statements(vec![*else_branch_value])?;
+                        self.execute_statements(vec![*else_branch_value])?
+                    } else {
+                        ControlFlow::Normal
+                    };
+
+                    if flow != ControlFlow::Normal {
+                        return Ok(flow);
                     }
                 }
-                Statement::While { condition, body } => {
+                Statement::While {
+                    condition,
+                    body,
+                    increment,
+                } => {
                     while bool::from(condition.evaluate(self.environment.clone())?) {
-                        self.interpret_statements(vec![*body.clone()])?; // fix here??
+                        match self.execute_statements(vec![*body.clone()])? {
+                            ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                            ControlFlow::Break => break,
+                            ControlFlow::Normal | ControlFlow::Continue => {}
+                        }
+
+                        if let Some(increment) = &increment {
+                            increment.evaluate(self.environment.clone())?;
+                        }
+                    }
+                }
+                Statement::ForIn {
+                    variable,
+                    iterable,
+                    body,
+                } => {
+                    let iterable = iterable.evaluate(self.environment.clone())?;
+                    let mut iterator = ValueIterator::new(iterable, variable.line)?;
+
+                    while let Some(value) = iterator.next()? {
+                        // a fresh variable for each pass, so closures keep their own value
+                        let mut environment = Environment::new();
+                        environment.define(String::from(&variable.lexeme), value)?;
+
+                        match self.execute_block(vec![*body.clone()], environment)? {
+                            ControlFlow::Return(value) => return Ok(ControlFlow::Return(value)),
+                            ControlFlow::Break => break,
+                            ControlFlow::Normal | ControlFlow::Continue => {}
+                        }
                     }
                 }
                 Statement::Function {
                     name,
                     parameters,
                     body,
+                    ..
+                } => {
+                    let function = Self::create_function(
+                        String::from(&name.lexeme),
+                        parameters,
+                        body,
+                        self.environment.clone(),
+                    );
+
+                    self.environment
+                        .borrow_mut()
+                        .define(String::from(&name.lexeme), function)?;
+                }
+                Statement::Class {
+                    name,
+                    superclass,
+                    methods,
                 } => {
-                    let arity = parameters.len();
-                    let body = body.clone();
-
-                    let closure = move |parent_environment: Rc<RefCell<Environment>>,
-                                        arguments: &Vec<LiteralValue>|
-                          -> Result<LiteralValue, String> {
-                        let mut closure_interpreter = Interpreter::for_closure(parent_environment);
-
-                        for (i, argument) in arguments.iter().enumerate() {
-                            closure_interpreter
-                                .environment
-                                .borrow_mut()
-                                .define(String::from(&parameters[i].lexeme), argument.clone());
+                    let superclass = match superclass {
+                        Some(Expression::Variable { token, .. }) if token.lexeme == name.lexeme => {
+                            return Err(format!(
+                                "A class {} can't inherit from itself",
+                                name.lexeme
+                            )
+                            .into());
                         }
+                        Some(expression) => match expression.evaluate(self.environment.clone())? {
+                            class @ LiteralValue::Class { .. } => Some(class),
+                            value => {
+                                return Err(format!(
+                                    "Superclass must be a class, got {}",
+                                    value.to_type()
+                                )
+                                .into())
+                            }
+                        },
+                        None => None,
+                    };
 
-                        for i in 0..(body.len()) {
-                            closure_interpreter.interpret_statements(vec![body[i].clone()])?;
+                    let mut class_environment = self.environment.clone();
 
-                            if let Ok(value) = closure_interpreter.specials.borrow().get("return") {
-                                return Ok(value);
-                            }
-                        }
+                    if let Some(superclass) = &superclass {
+                        let mut super_environment = Environment::new();
+                        super_environment.enclosing = Some(self.environment.clone());
+                        super_environment.define(String::from("super"), superclass.clone())?;
 
-                        Ok(LiteralValue::Nil)
-                    };
+                        class_environment = Rc::new(RefCell::new(super_environment));
+                    }
+
+                    let mut class_methods = HashMap::new();
+
+                    for method in methods {
+                        if let Statement::Function {
+                            name,
+                            parameters,
+                            body,
+                            ..
+                        } = method
+                        {
+                            let function = Self::create_function(
+                                String::from(&name.lexeme),
+                                parameters,
+                                body,
+                                class_environment.clone(),
+                            );
+                            class_methods.insert(String::from(&name.lexeme), function);
+                        }
+                    }
 
                     self.environment.borrow_mut().define(
                         String::from(&name.lexeme),
-                        LiteralValue::Callable {
+                        LiteralValue::Class {
                             name: String::from(&name.lexeme),
-                            arity,
-                            fun: Rc::new(closure),
+                            methods: Rc::new(class_methods),
+                            superclass: superclass.map(Box::new),
                         },
-                    );
+                    )?;
                 }
                 Statement::Return { keyword: _, value } => {
                     let response = match value {
@@ -151,13 +307,230 @@ impl Interpreter {
                         _ => LiteralValue::Nil,
                     };
 
-                    self.specials
-                        .borrow_mut()
-                        .define(String::from("return"), response);
+                    return Ok(ControlFlow::Return(response));
+                }
+                Statement::Match {
+                    keyword: _,
+                    subject,
+                    arms,
+                } => {
+                    let value = subject.evaluate(self.environment.clone())?;
+
+                    let arm = arms.into_iter().find(|(patterns, _)| {
+                        patterns.iter().any(|pattern| pattern.matches(&value))
+                    });
+
+                    if let Some((_, body)) = arm {
+                        let flow = self.execute_statements(vec![body])?;
+
+                        if flow != ControlFlow::Normal {
+                            return Ok(flow);
+                        }
+                    }
+                }
+                Statement::Import {
+                    keyword,
+                    path,
+                    name,
+                } => {
+                    let module =
+                        self.import(&LiteralValue::from(path).to_string(), keyword.line)?;
+                    self.environment.borrow_mut().define(name.lexeme, module)?;
                 }
+                Statement::Yield { keyword, .. } => {
+                    // functions holding a yield are run by a generator instead
+                    return Err(format!(
+                        "Can't yield outside of a generator at line {}",
+                        keyword.line
+                    )
+                    .into());
+                }
+                Statement::Throw { keyword, value } => {
+                    let value = value.evaluate(self.environment.clone())?;
+
+                    return Err(RuntimeError {
+                        message: format!("Uncaught {} at line {}", value, keyword.line),
+                        value: Some(value),
+                    });
+                }
+                Statement::Try {
+                    keyword,
+                    body,
+                    catch,
+                    finally,
+                } => {
+                    let mut result = self.execute_block(body, Environment::new());
+
+                    if let Some((name, statements)) = catch {
+                        if let Err(error) = result {
+                            let mut catch_environment = Environment::new();
+                            catch_environment
+                                .define(name.lexeme, Self::caught(error, keyword.line))?;
+
+                            result = self.execute_block(statements, catch_environment);
+                        }
+                    }
+
+                    if let Some(statements) = finally {
+                        let flow = self.execute_block(statements, Environment::new())?;
+
+                        // leaving the finally block early overrides the outcome of try and catch
+                        if flow != ControlFlow::Normal {
+                            return Ok(flow);
+                        }
+                    }
+
+                    let flow = result?;
+
+                    if flow != ControlFlow::Normal {
+                        return Ok(flow);
+                    }
+                }
+                Statement::Break { keyword: _ } => return Ok(ControlFlow::Break),
+                Statement::Continue { keyword: _ } => return Ok(ControlFlow::Continue),
             };
         }
 
-        Ok(())
+        Ok(ControlFlow::Normal)
+    }
+
+    fn execute_block(
+        &mut self,
+        statements: Vec<Statement>,
+        mut environment: Environment,
+    ) -> Result<ControlFlow, RuntimeError> {
+        environment.enclosing = Some(self.environment.clone());
+
+        let old_environment = self.environment.clone();
+        self.environment = Rc::new(RefCell::new(environment));
+        let block_result = self.execute_statements(statements);
+        self.environment = old_environment;
+
+        block_result
+    }
+
+    fn import(&mut self, path: &str, line: usize) -> Result<LiteralValue, RuntimeError> {
+        let file = match &self.path {
+            Some(importer) => importer.with_file_name(path),
+            None => PathBuf::from(path),
+        };
+
+        let file = match fs::canonicalize(file) {
+            Ok(file) => file,
+            Err(message) => {
+                return Err(format!("Cannot import {} at line {}: {}", path, line, message).into())
+            }
+        };
+
+        match self.modules.borrow().get(&file) {
+            Some(Some(module)) => return Ok(module.clone()),
+            Some(None) => {
+                return Err(format!("Circular import of {} at line {}", path, line).into())
+            }
+            None => {}
+        }
+
+        let source = match fs::read_to_string(&file) {
+            Ok(source) => source,
+            Err(message) => {
+                return Err(format!("Cannot import {} at line {}: {}", path, line, message).into())
+            }
+        };
+
+        self.modules.borrow_mut().insert(file.clone(), None);
+
+        // natives go in an enclosing environment, so only the module's own bindings are members
+        let mut module_interpreter = Interpreter::for_closure(Interpreter::new().environment);
+        module_interpreter.path = Some(file.clone());
+        module_interpreter.modules = self.modules.clone();
+
+        if let Err(mut error) = module_interpreter.run_source(&source) {
+            self.modules.borrow_mut().remove(&file);
+            error.message = format!("In module {}: {}", path, error.message);
+            return Err(error);
+        }
+
+        let module = LiteralValue::Module {
+            name: String::from(path),
+            environment: module_interpreter.environment,
+        };
+        self.modules.borrow_mut().insert(file, Some(module.clone()));
+
+        Ok(module)
+    }
+
+    // errors that weren't thrown and never passed an expression with a line get the line of the try
+    pub fn caught(error: RuntimeError, line: usize) -> LiteralValue {
+        error.value.unwrap_or(LiteralValue::ErrorValue {
+            message: error.message,
+            line,
+        })
+    }
+
+    pub fn create_function(
+        name: String,
+        parameters: Vec<Parameter>,
+        body: Vec<Statement>,
+        environment: Rc<RefCell<Environment>>,
+    ) -> LiteralValue {
+        let arity = parameters
+            .iter()
+            .filter(|parameter| parameter.default.is_none() && !parameter.rest)
+            .count();
+        let max_arity = match parameters.last() {
+            Some(parameter) if parameter.rest => None,
+            _ => Some(parameters.len()),
+        };
+        let signature = Rc::new(parameters.clone());
+        let is_generator = body.iter().any(Statement::contains_yield);
+        let function_name = name.clone();
+
+        let closure = move |parent_environment: Rc<RefCell<Environment>>,
+                            arguments: &Vec<LiteralValue>|
+              -> Result<LiteralValue, RuntimeError> {
+            let mut closure_interpreter = Interpreter::for_closure(parent_environment);
+
+            for (i, parameter) in parameters.iter().enumerate() {
+                let value = if parameter.rest {
+                    let rest = arguments.get(i..).unwrap_or_default().to_vec();
+                    LiteralValue::ListValue(Rc::new(RefCell::new(rest)))
+                } else if let Some(argument) = arguments.get(i) {
+                    argument.clone()
+                } else if let Some(default) = &parameter.default {
+                    default.evaluate(closure_interpreter.environment.clone())?
+                } else {
+                    LiteralValue::Nil
+                };
+
+                closure_interpreter
+                    .environment
+                    .borrow_mut()
+                    .define(String::from(&parameter.name.lexeme), value)?;
+            }
+
+            if is_generator {
+                let generator = Generator::new(
+                    function_name.clone(),
+                    body.clone(),
+                    closure_interpreter.environment,
+                );
+
+                return Ok(LiteralValue::Generator(Rc::new(RefCell::new(generator))));
+            }
+
+            match closure_interpreter.execute_statements(body.clone())? {
+                ControlFlow::Return(value) => Ok(value),
+                _ => Ok(LiteralValue::Nil),
+            }
+        };
+
+        LiteralValue::Callable {
+            name,
+            arity,
+            max_arity,
+            parameters: signature,
+            fun: Rc::new(closure),
+            environment,
+        }
     }
 }
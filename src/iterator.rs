@@ -0,0 +1,103 @@
+use crate::environment::Environment;
+use crate::expression::Expression;
+use crate::expression_literal_value::LiteralValue;
+use crate::interpreter::RuntimeError;
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+#[cfg(test)]
+#[path = "./tests/iterator_tests.rs"]
+mod tests;
+
+// the values a for-in loop walks over
+pub enum ValueIterator {
+    // read one index at a time, so items pushed while looping are visited as well
+    List {
+        items: Rc<RefCell<Vec<LiteralValue>>>,
+        index: usize,
+    },
+    // map keys and string characters are taken up front, range numbers as they are reached
+    Values(Box<dyn Iterator<Item = LiteralValue>>),
+    // anything answering has_next() and next(), like generators
+    Protocol(LiteralValue),
+}
+
+impl ValueIterator {
+    pub fn new(value: LiteralValue, line: usize) -> Result<Self, RuntimeError> {
+        match value {
+            LiteralValue::ListValue(items) => Ok(Self::List { items, index: 0 }),
+            LiteralValue::MapValue(_) => Ok(Self::Values(Box::new(
+                value.map_entries().into_iter().map(|(key, _)| key),
+            ))),
+            LiteralValue::StringValue(string) => Ok(Self::Values(Box::new(
+                string
+                    .chars()
+                    .map(|character| LiteralValue::StringValue(character.to_string()))
+                    .collect::<Vec<LiteralValue>>()
+                    .into_iter(),
+            ))),
+            LiteralValue::RangeValue { .. } => Ok(Self::Values(Box::new(
+                value.range_numbers().map(LiteralValue::IntValue),
+            ))),
+            LiteralValue::Generator(_) => Ok(Self::Protocol(value)),
+            LiteralValue::Instance { ref class, .. } => {
+                // an iterator() method hands out the object to walk, otherwise the instance is one
+                if class.find_method("iterator").is_some() {
+                    let iterator = Self::call_method(&value, "iterator")?;
+                    return Self::new(iterator, line);
+                }
+
+                if class.find_method("has_next").is_some() && class.find_method("next").is_some() {
+                    return Ok(Self::Protocol(value));
+                }
+
+                Err(format!(
+                    "Can't iterate over instance without iterator or has_next and next methods at line {}",
+                    line
+                ).into())
+            }
+            _ => Err(format!("Can't iterate over {} at line {}", value.to_type(), line).into()),
+        }
+    }
+
+    pub fn next(&mut self) -> Result<Option<LiteralValue>, RuntimeError> {
+        match self {
+            Self::List { items, index } => {
+                let item = items.borrow().get(*index).cloned();
+                *index += 1;
+                Ok(item)
+            }
+            Self::Values(values) => Ok(values.next()),
+            Self::Protocol(object) => {
+                if bool::from(Self::call_method(object, "has_next")?) {
+                    Ok(Some(Self::call_method(object, "next")?))
+                } else {
+                    Ok(None)
+                }
+            }
+        }
+    }
+
+    fn call_method(object: &LiteralValue, name: &str) -> Result<LiteralValue, RuntimeError> {
+        let method = object.get_property(name)?;
+
+        match method {
+            LiteralValue::Callable {
+                ref fun,
+                environment: ref closure,
+                ..
+            } => {
+                let arguments = Expression::call_arguments(
+                    &method,
+                    &[],
+                    &[],
+                    Rc::new(RefCell::new(Environment::new())),
+                )?;
+
+                fun(closure.clone(), &arguments)
+            }
+            _ => Err(format!("Cannot use {} as callable", method.to_type()).into()),
+        }
+    }
+}
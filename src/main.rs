@@ -1,15 +1,18 @@
 mod environment;
 mod expression;
 mod expression_literal_value;
+mod generator;
 mod interpreter;
+mod iterator;
+mod natives;
 mod parser;
+mod resolver;
 mod scanner;
 mod statement;
 mod token;
+mod typechecker;
 
 use crate::interpreter::Interpreter;
-use crate::parser::Parser;
-use crate::scanner::Scanner;
 
 use std::env;
 use std::fs;
@@ -21,10 +24,10 @@ use std::process::exit;
 mod tests;
 
 fn run_file(path: &str) -> Result<(), String> {
-    let mut interpreter: Interpreter = Interpreter::new();
+    let mut interpreter: Interpreter = Interpreter::for_file(path);
     match fs::read_to_string(path) {
         Err(message) => Err(message.to_string()),
-        Ok(contents) => run(&mut interpreter, &contents),
+        Ok(contents) => interpreter.run(&contents),
     }
 }
 
@@ -52,24 +55,12 @@ fn run_prompt() -> Result<(), String> {
             break Ok(());
         }
 
-        if let Err(message) = run(&mut interpreter, &input) {
+        if let Err(message) = interpreter.run(&input) {
             println!("{}", message);
         }
     }
 }
 
-fn run(interpreter: &mut Interpreter, source: &str) -> Result<(), String> {
-    let mut scanner = Scanner::new(source);
-    let tokens = scanner.scan_tokens()?;
-
-    let mut parser = Parser::new(tokens);
-    let statements = parser.parse()?;
-
-    interpreter.interpret_statements(statements)?;
-
-    Ok(())
-}
-
 fn main() {
     let args: Vec<String> = env::args().collect();
 
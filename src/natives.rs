@@ -0,0 +1,187 @@
+use crate::environment::Environment;
+use crate::expression_literal_value::LiteralValue::{self, *};
+use crate::expression_literal_value::MapKey;
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::SystemTime;
+
+#[cfg(test)]
+#[path = "./tests/natives_tests.rs"]
+mod tests;
+
+type NativeFunction =
+    fn(Rc<RefCell<Environment>>, &Vec<LiteralValue>) -> Result<LiteralValue, String>;
+
+pub fn define_natives(environment: &mut Environment) {
+    let natives: Vec<(&str, usize, NativeFunction)> = vec![
+        ("clock", 0, clock_impl),
+        ("len", 1, len_impl),
+        ("push", 2, push_impl),
+        ("pop", 1, pop_impl),
+        ("insert", 3, insert_impl),
+        ("remove", 2, remove_impl),
+        ("keys", 1, keys_impl),
+        ("values", 1, values_impl),
+        ("has", 2, has_impl),
+        ("delete", 2, delete_impl),
+    ];
+
+    for (name, arity, fun) in natives {
+        environment.values.insert(
+            String::from(name),
+            Callable {
+                name: String::from(name),
+                arity,
+                max_arity: Some(arity),
+                parameters: Rc::new(vec![]),
+                fun: Rc::new(move |environment, arguments| Ok(fun(environment, arguments)?)),
+                environment: Rc::new(RefCell::new(Environment::new())),
+            },
+        );
+    }
+}
+
+fn clock_impl(
+    _env: Rc<RefCell<Environment>>,
+    _args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let now = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .expect("Could not get time")
+        .as_secs();
+
+    Ok(IntValue(now as i64))
+}
+
+fn len_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    match &args[0] {
+        ListValue(elements) => Ok(IntValue(elements.borrow().len() as i64)),
+        MapValue(entries) => Ok(IntValue(entries.borrow().len() as i64)),
+        StringValue(string) => Ok(IntValue(string.chars().count() as i64)),
+        value => Err(format!(
+            "len expects List, Map or String, got {}",
+            value.to_type()
+        )),
+    }
+}
+
+fn push_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let elements = expect_list("push", &args[0])?;
+    elements.borrow_mut().push(args[1].clone());
+
+    Ok(Nil)
+}
+
+fn pop_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let elements = expect_list("pop", &args[0])?;
+    let value = elements.borrow_mut().pop();
+
+    value.ok_or(String::from("Cannot pop from an empty list"))
+}
+
+fn insert_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let elements = expect_list("insert", &args[0])?;
+    let length = elements.borrow().len();
+
+    // inserting right after the last element is allowed
+    let position = match &args[1] {
+        IntValue(index) if *index == length as i64 => length,
+        index => LiteralValue::list_position(index, length)?,
+    };
+
+    elements.borrow_mut().insert(position, args[2].clone());
+
+    Ok(Nil)
+}
+
+fn remove_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let elements = expect_list("remove", &args[0])?;
+    let position = LiteralValue::list_position(&args[1], elements.borrow().len())?;
+    let value = elements.borrow_mut().remove(position);
+
+    Ok(value)
+}
+
+fn keys_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    expect_map("keys", &args[0])?;
+    let keys = args[0]
+        .map_entries()
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+
+    Ok(ListValue(Rc::new(RefCell::new(keys))))
+}
+
+fn values_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    expect_map("values", &args[0])?;
+    let values = args[0]
+        .map_entries()
+        .into_iter()
+        .map(|(_, value)| value)
+        .collect();
+
+    Ok(ListValue(Rc::new(RefCell::new(values))))
+}
+
+fn has_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let entries = expect_map("has", &args[0])?;
+    let key = MapKey::try_from(&args[1])?;
+    let has_key = entries.borrow().contains_key(&key);
+
+    Ok(LiteralValue::from(has_key))
+}
+
+fn delete_impl(
+    _env: Rc<RefCell<Environment>>,
+    args: &Vec<LiteralValue>,
+) -> Result<LiteralValue, String> {
+    let entries = expect_map("delete", &args[0])?;
+    let key = MapKey::try_from(&args[1])?;
+    let deleted = entries.borrow_mut().remove(&key).is_some();
+
+    Ok(LiteralValue::from(deleted))
+}
+
+fn expect_list(name: &str, value: &LiteralValue) -> Result<Rc<RefCell<Vec<LiteralValue>>>, String> {
+    match value {
+        ListValue(elements) => Ok(elements.clone()),
+        _ => Err(format!("{} expects a List, got {}", name, value.to_type())),
+    }
+}
+
+fn expect_map(
+    name: &str,
+    value: &LiteralValue,
+) -> Result<Rc<RefCell<HashMap<MapKey, LiteralValue>>>, String> {
+    match value {
+        MapValue(entries) => Ok(entries.clone()),
+        _ => Err(format!("{} expects a Map, got {}", name, value.to_type())),
+    }
+}
@@ -1,7 +1,7 @@
 use crate::expression::Expression;
 use crate::expression::Expression::*;
 use crate::expression_literal_value::LiteralValue;
-use crate::statement::Statement;
+use crate::statement::{Parameter, Pattern, Statement};
 use crate::token::TokenType::*;
 use crate::token::{Token, TokenType};
 
@@ -13,9 +13,13 @@ use std::string::String;
 #[path = "./tests/parser_tests.rs"]
 mod tests;
 
+// parameters, return type annotation and body
+type FunctionParts = (Vec<Parameter>, Option<Token>, Vec<Statement>);
+
 enum FunctionKind {
     Function,
     Method,
+    Lambda,
 }
 
 impl Display for FunctionKind {
@@ -23,6 +27,7 @@ impl Display for FunctionKind {
         match self {
             Self::Function => write!(f, "Function"),
             Self::Method => write!(f, "Method"),
+            Self::Lambda => write!(f, "Lambda"),
         }
     }
 }
@@ -30,11 +35,18 @@ impl Display for FunctionKind {
 pub struct Parser<'a> {
     tokens: &'a Vec<Token>,
     current: usize,
+    loop_depth: usize,
+    pub warnings: Vec<String>,
 }
 
 impl<'a> Parser<'a> {
     pub fn new(tokens: &'a Vec<Token>) -> Self {
-        Self { tokens, current: 0 }
+        Self {
+            tokens,
+            current: 0,
+            loop_depth: 0,
+            warnings: vec![],
+        }
     }
 
     // region grammar components
@@ -59,7 +71,19 @@ impl<'a> Parser<'a> {
     }
 
     fn declaration(&mut self) -> Result<Statement, String> {
-        if self.match_tokens(vec![Fun]) {
+        if self.match_tokens(vec![Class]) {
+            return match self.class_declaration() {
+                Ok(statement) => Ok(statement),
+                Err(message) => {
+                    self.synchronize();
+                    Err(message)
+                }
+            };
+        }
+
+        // 'fun' without a name is an anonymous function expression
+        if self.check(Fun) && self.check_next(Identifier) {
+            self.advance();
             return match self.function(&FunctionKind::Function) {
                 Ok(statement) => Ok(statement),
                 Err(message) => {
@@ -79,7 +103,50 @@ impl<'a> Parser<'a> {
             };
         }
 
-        self.statement()
+        if self.match_tokens(vec![Const]) {
+            return match self.const_declaration() {
+                Ok(statement) => Ok(statement),
+                Err(message) => {
+                    self.synchronize();
+                    Err(message)
+                }
+            };
+        }
+
+        match self.statement() {
+            Ok(statement) => Ok(statement),
+            Err(message) => {
+                self.synchronize();
+                Err(message)
+            }
+        }
+    }
+
+    fn class_declaration(&mut self) -> Result<Statement, String> {
+        let name = self.consume(Identifier, "Expect class name")?;
+
+        let mut superclass = None;
+
+        if self.match_tokens(vec![Less]) {
+            let token = self.consume(Identifier, "Expect superclass name")?;
+            superclass = Some(Variable { token, depth: None });
+        }
+
+        self.consume(LeftBrace, "Expect '{' before class body")?;
+
+        let mut methods = vec![];
+
+        while !self.check(RightBrace) && !self.is_at_end() {
+            methods.push(self.function(&FunctionKind::Method)?);
+        }
+
+        self.consume(RightBrace, "Expect '}' after class body")?;
+
+        Ok(Statement::Class {
+            name,
+            superclass,
+            methods,
+        })
     }
 
     fn function(&mut self, kind: &FunctionKind) -> Result<Statement, String> {
@@ -87,7 +154,33 @@ impl<'a> Parser<'a> {
 
         self.consume(LeftParen, &format!("Expect '(' after {} name", kind))?;
 
-        let mut parameters = vec![];
+        let (parameters, return_type, body) = self.function_body(kind)?;
+
+        Ok(Statement::Function {
+            name: function_name,
+            parameters,
+            return_type,
+            body,
+        })
+    }
+
+    fn lambda(&mut self) -> Result<Expression, String> {
+        let keyword = self.previous();
+
+        self.consume(LeftParen, "Expect '(' after 'fun'")?;
+
+        let (parameters, return_type, body) = self.function_body(&FunctionKind::Lambda)?;
+
+        Ok(Lambda {
+            keyword,
+            parameters,
+            return_type,
+            body,
+        })
+    }
+
+    fn function_body(&mut self, kind: &FunctionKind) -> Result<FunctionParts, String> {
+        let mut parameters: Vec<Parameter> = vec![];
 
         if !self.check(RightParen) {
             loop {
@@ -95,7 +188,38 @@ impl<'a> Parser<'a> {
                     return Err(String::from("Can't have more than 255 parameters."));
                 }
 
-                parameters.push(self.consume(Identifier, "Expect parameter name")?);
+                if let Some(rest) = parameters.iter().find(|parameter| parameter.rest) {
+                    return Err(format!(
+                        "Rest parameter {} must be the last parameter at line {}",
+                        rest.name.lexeme, rest.name.line
+                    ));
+                }
+
+                let rest = self.match_tokens(vec![DotDotDot]);
+                let name = self.consume(Identifier, "Expect parameter name")?;
+                let annotation = self.type_annotation()?;
+
+                let mut default = None;
+
+                if !rest && self.match_tokens(vec![Equal]) {
+                    default = Some(self.expression()?);
+                } else if !rest
+                    && parameters
+                        .iter()
+                        .any(|parameter| parameter.default.is_some())
+                {
+                    return Err(format!(
+                        "Parameter {} without default value follows one with default at line {}",
+                        name.lexeme, name.line
+                    ));
+                }
+
+                parameters.push(Parameter {
+                    name,
+                    annotation,
+                    default,
+                    rest,
+                });
 
                 if !self.match_tokens(vec![Comma]) {
                     break;
@@ -105,19 +229,37 @@ impl<'a> Parser<'a> {
 
         self.consume(RightParen, "Expect ')' after parameters")?;
 
+        let mut return_type = None;
+        if self.match_tokens(vec![Arrow]) {
+            return_type = Some(self.consume(Identifier, "Expect return type after '->'")?);
+        }
+
         self.consume(LeftBrace, &format!("Expect '{{' before {} body", kind))?;
 
-        let body = self.blocks()?;
+        // loops outside of the function can't be broken out of from its body
+        let enclosing_loop_depth = self.loop_depth;
+        self.loop_depth = 0;
+        let body = self.blocks();
+        self.loop_depth = enclosing_loop_depth;
+        let body = body?;
 
-        Ok(Statement::Function {
-            name: function_name,
-            parameters: parameters,
-            body: body,
-        })
+        Ok((parameters, return_type, body))
+    }
+
+    // annotations are only read by the type checker, the interpreter ignores them
+    fn type_annotation(&mut self) -> Result<Option<Token>, String> {
+        if self.match_tokens(vec![Colon]) {
+            return Ok(Some(
+                self.consume(Identifier, "Expect type name after ':'")?,
+            ));
+        }
+
+        Ok(None)
     }
 
     fn variable_declaration(&mut self) -> Result<Statement, String> {
         let token_name = self.consume(Identifier, "Expect variable name")?;
+        let annotation = self.type_annotation()?;
 
         let mut initializer: Expression = Literal {
             value: LiteralValue::Nil,
@@ -131,11 +273,30 @@ impl<'a> Parser<'a> {
 
         Ok(Statement::Variable {
             token: token_name,
+            annotation,
             initializer: initializer,
         })
     }
 
+    fn const_declaration(&mut self) -> Result<Statement, String> {
+        let token = self.consume(Identifier, "Expect constant name")?;
+        let annotation = self.type_annotation()?;
+        self.consume(Equal, "Expected '=' after constant name")?;
+        let initializer = self.expression()?;
+        self.consume(Semicolon, "Expected ';' after a constant declaration")?;
+
+        Ok(Statement::Const {
+            token,
+            annotation,
+            initializer,
+        })
+    }
+
     fn statement(&mut self) -> Result<Statement, String> {
+        if self.is_map_literal() {
+            return self.expression_statement();
+        }
+
         if self.match_tokens(vec![LeftBrace]) {
             let blocks = self.blocks()?;
             return Ok(Statement::Block { statements: blocks });
@@ -153,6 +314,26 @@ impl<'a> Parser<'a> {
             return self.if_statement();
         }
 
+        if self.match_tokens(vec![Match]) {
+            return self.match_statement();
+        }
+
+        if self.match_tokens(vec![Import]) {
+            return self.import_statement();
+        }
+
+        if self.match_tokens(vec![Try]) {
+            return self.try_statement();
+        }
+
+        if self.match_tokens(vec![Throw]) {
+            return self.throw_statement();
+        }
+
+        if self.match_tokens(vec![Yield]) {
+            return self.yield_statement();
+        }
+
         if self.match_tokens(vec![Print]) {
             return self.print_statement();
         }
@@ -161,6 +342,10 @@ impl<'a> Parser<'a> {
             return self.return_statement();
         }
 
+        if self.match_tokens(vec![Break, Continue]) {
+            return self.loop_control_statement();
+        }
+
         self.expression_statement()
     }
 
@@ -182,15 +367,23 @@ impl<'a> Parser<'a> {
         let condition = self.expression()?;
         self.consume(RightParen, "Expected ')' after while condition")?;
 
-        let body_statement = self.statement()?;
+        let body_statement = self.loop_body()?;
         let body = Box::new(body_statement);
 
-        Ok(Statement::While { condition, body })
+        Ok(Statement::While {
+            condition,
+            body,
+            increment: None,
+        })
     }
 
     fn for_statement(&mut self) -> Result<Statement, String> {
         self.consume(LeftParen, "Expected '(' after 'for")?;
 
+        if self.check(Identifier) && self.check_next(In) {
+            return self.for_in_statement();
+        }
+
         let initializer: Option<Statement>;
 
         if self.match_tokens(vec![Semicolon]) {
@@ -222,22 +415,13 @@ impl<'a> Parser<'a> {
 
         self.consume(RightParen, "Expect ')' after for clauses")?;
 
-        let mut body = self.statement()?;
-
-        if let Some(increment) = increment {
-            body = Statement::Block {
-                statements: vec![
-                    body,
-                    Statement::Expression {
-                        expression: increment,
-                    },
-                ],
-            };
-        }
+        let mut body = self.loop_body()?;
 
+        // increment is kept apart from the body, so that 'continue' still runs it
         body = Statement::While {
-            condition: condition,
+            condition,
             body: Box::new(body),
+            increment,
         };
 
         if let Some(initializer) = initializer {
@@ -249,6 +433,23 @@ impl<'a> Parser<'a> {
         Ok(body)
     }
 
+    fn for_in_statement(&mut self) -> Result<Statement, String> {
+        let variable = self.advance();
+        self.advance();
+
+        let iterable = self.expression()?;
+
+        self.consume(RightParen, "Expect ')' after for-in iterable")?;
+
+        let body = self.loop_body()?;
+
+        Ok(Statement::ForIn {
+            variable,
+            iterable,
+            body: Box::new(body),
+        })
+    }
+
     fn if_statement(&mut self) -> Result<Statement, String> {
         self.consume(LeftParen, "Expected '(' after 'if'")?;
         let condition = self.expression()?;
@@ -271,6 +472,145 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn match_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+        self.consume(LeftParen, "Expected '(' after 'match'")?;
+        let subject = self.expression()?;
+        self.consume(RightParen, "Expected ')' after match subject")?;
+        self.consume(LeftBrace, "Expected '{' before match arms")?;
+
+        let mut arms = vec![];
+        let mut wildcard_line = None;
+
+        while !self.check(RightBrace) && !self.is_at_end() {
+            let line = self.peek().line;
+
+            if let Some(wildcard_line) = wildcard_line {
+                self.warnings.push(format!(
+                    "Unreachable match arm at line {} after wildcard at line {}",
+                    line, wildcard_line
+                ));
+            }
+
+            let mut patterns = vec![self.pattern()?];
+            while self.match_tokens(vec![Pipe]) {
+                patterns.push(self.pattern()?);
+            }
+
+            if wildcard_line.is_none() && patterns.contains(&Pattern::Wildcard) {
+                wildcard_line = Some(line);
+            }
+
+            self.consume(FatArrow, "Expected '=>' after match pattern")?;
+            let body = self.statement()?;
+            self.match_tokens(vec![Comma]);
+
+            arms.push((patterns, body));
+        }
+
+        self.consume(RightBrace, "Expected '}' after match arms")?;
+
+        Ok(Statement::Match {
+            keyword,
+            subject,
+            arms,
+        })
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, String> {
+        if self.check(Identifier) && self.peek().lexeme == "_" {
+            self.advance();
+            return Ok(Pattern::Wildcard);
+        }
+
+        let start = self.pattern_literal()?;
+
+        if self.match_tokens(vec![DotDot, DotDotEqual]) {
+            let inclusive = self.previous().token_type == DotDotEqual;
+            let end = self.pattern_literal()?;
+
+            let mut step = None;
+            if self.match_step() {
+                let line = self.previous().line;
+
+                step = match (&start, &end, self.pattern_literal()?) {
+                    (
+                        LiteralValue::IntValue(_),
+                        LiteralValue::IntValue(_),
+                        LiteralValue::IntValue(step),
+                    ) if step != 0 => Some(step),
+                    _ => {
+                        return Err(format!(
+                            "Range pattern with step needs Int bounds and a non-zero Int step at line {}",
+                            line
+                        ))
+                    }
+                };
+            }
+
+            return Ok(Pattern::Range {
+                start,
+                end,
+                inclusive,
+                step,
+            });
+        }
+
+        Ok(Pattern::Literal(start))
+    }
+
+    fn pattern_literal(&mut self) -> Result<LiteralValue, String> {
+        if self.match_tokens(vec![Minus]) {
+            let number = self.consume(Number, "Expected number after '-' in pattern")?;
+
+            return match LiteralValue::from(number) {
+                LiteralValue::IntValue(value) => Ok(LiteralValue::IntValue(-value)),
+                LiteralValue::FValue(value) => Ok(LiteralValue::FValue(-value)),
+                value => Ok(value),
+            };
+        }
+
+        if self.match_tokens(vec![Number, String, True, False, Nil]) {
+            return Ok(LiteralValue::from(self.previous()));
+        }
+
+        Err(format!(
+            "Expected literal pattern at line: {}, literal: {}",
+            self.peek().line,
+            self.peek().lexeme
+        ))
+    }
+
+    fn loop_body(&mut self) -> Result<Statement, String> {
+        self.loop_depth += 1;
+        let body = self.statement();
+        self.loop_depth -= 1;
+
+        body
+    }
+
+    fn loop_control_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+
+        if self.loop_depth == 0 {
+            return Err(format!(
+                "Can't use '{}' outside of a loop at line {}",
+                keyword.lexeme, keyword.line
+            ));
+        }
+
+        self.consume(
+            Semicolon,
+            &format!("Expected ';' after '{}'", keyword.lexeme),
+        )?;
+
+        if keyword.token_type == Break {
+            Ok(Statement::Break { keyword })
+        } else {
+            Ok(Statement::Continue { keyword })
+        }
+    }
+
     fn print_statement(&mut self) -> Result<Statement, String> {
         let expression = self.expression()?;
         self.consume(Semicolon, "Expected ';' after the value.")?;
@@ -292,6 +632,88 @@ impl<'a> Parser<'a> {
         Ok(Statement::Return { keyword, value })
     }
 
+    fn import_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+        let path = self.consume(String, "Expected module path after 'import'")?;
+
+        // 'as' stays usable as a name everywhere else
+        if !(self.check(Identifier) && self.peek().lexeme == "as") {
+            return Err(format!(
+                "Expected 'as' after module path at line {}",
+                path.line
+            ));
+        }
+        self.advance();
+
+        let name = self.consume(Identifier, "Expected module name after 'as'")?;
+        self.consume(Semicolon, "Expected ';' after import")?;
+
+        Ok(Statement::Import {
+            keyword,
+            path,
+            name,
+        })
+    }
+
+    fn yield_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+
+        let mut value = None;
+
+        if !self.check(Semicolon) {
+            value = Some(self.expression()?);
+        }
+
+        self.consume(Semicolon, "Expected ';' after yielded value")?;
+
+        Ok(Statement::Yield { keyword, value })
+    }
+
+    fn throw_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+        let value = self.expression()?;
+        self.consume(Semicolon, "Expected ';' after thrown value")?;
+
+        Ok(Statement::Throw { keyword, value })
+    }
+
+    fn try_statement(&mut self) -> Result<Statement, String> {
+        let keyword = self.previous();
+        self.consume(LeftBrace, "Expected '{' after 'try'")?;
+        let body = self.blocks()?;
+
+        let mut catch = None;
+
+        if self.match_tokens(vec![Catch]) {
+            self.consume(LeftParen, "Expected '(' after 'catch'")?;
+            let name = self.consume(Identifier, "Expected error variable name")?;
+            self.consume(RightParen, "Expected ')' after error variable name")?;
+            self.consume(LeftBrace, "Expected '{' before catch body")?;
+            catch = Some((name, self.blocks()?));
+        }
+
+        let mut finally = None;
+
+        if self.match_tokens(vec![Finally]) {
+            self.consume(LeftBrace, "Expected '{' after 'finally'")?;
+            finally = Some(self.blocks()?);
+        }
+
+        if catch.is_none() && finally.is_none() {
+            return Err(format!(
+                "Expected 'catch' or 'finally' after try block at line {}",
+                keyword.line
+            ));
+        }
+
+        Ok(Statement::Try {
+            keyword,
+            body,
+            catch,
+            finally,
+        })
+    }
+
     fn expression_statement(&mut self) -> Result<Statement, String> {
         let expression = self.expression()?;
         self.consume(Semicolon, "Expected ';' after the value.")?;
@@ -304,23 +726,111 @@ impl<'a> Parser<'a> {
     }
 
     pub fn assignment(&mut self) -> Result<Expression, String> {
-        let expression = self.or()?;
+        let expression = self.conditional()?;
 
         if self.match_tokens(vec![Equal]) {
             let equals = self.previous();
             let value = self.assignment()?;
 
-            return match expression {
-                Variable { token } => Ok(Assign {
-                    name: token.lexeme,
-                    value: Box::new(value),
-                }),
-                _ => Err(format!("Invalid assignment target {}", equals.lexeme)),
-            };
+            return Self::assignment_target(expression, value, None, &equals);
+        }
+
+        if self.match_tokens(vec![PlusEqual, MinusEqual, StarEqual, SlashEqual]) {
+            let equals = self.previous();
+            let value = self.assignment()?;
+
+            return Self::assignment_target(
+                expression,
+                value,
+                Some(Self::compound_operator(&equals)),
+                &equals,
+            );
         }
+
         Ok(expression)
     }
 
+    // compound assignment on a variable becomes a plain assignment of a binary expression,
+    // properties and indexes keep the operator so that their object is evaluated once
+    fn assignment_target(
+        target: Expression,
+        value: Expression,
+        operator: Option<Token>,
+        equals: &Token,
+    ) -> Result<Expression, String> {
+        match target {
+            Variable { token, .. } => {
+                let value = match operator {
+                    Some(operator) => Binary {
+                        left: Box::new(Variable {
+                            token: token.clone(),
+                            depth: None,
+                        }),
+                        operator,
+                        right: Box::new(value),
+                    },
+                    None => value,
+                };
+
+                Ok(Assign {
+                    name: token,
+                    value: Box::new(value),
+                    depth: None,
+                })
+            }
+            Get { object, name } => Ok(Set {
+                object,
+                name,
+                value: Box::new(value),
+                operator,
+            }),
+            Index {
+                object,
+                bracket,
+                index,
+            } => Ok(SetIndex {
+                object,
+                bracket,
+                index,
+                value: Box::new(value),
+                operator,
+            }),
+            _ => Err(format!("Invalid assignment target {}", equals.lexeme)),
+        }
+    }
+
+    fn compound_operator(token: &Token) -> Token {
+        let token_type = match token.token_type {
+            PlusEqual | PlusPlus => Plus,
+            MinusEqual | MinusMinus => Minus,
+            StarEqual => Star,
+            _ => Slash,
+        };
+
+        Token::new(token_type, token.lexeme[..1].to_string(), None, token.line)
+    }
+
+    fn conditional(&mut self) -> Result<Expression, String> {
+        let condition = self.or()?;
+
+        if self.match_tokens(vec![Question]) {
+            let then_branch = self.expression()?;
+            self.consume(
+                Colon,
+                "Expected ':' after then branch of conditional expression",
+            )?;
+            let else_branch = self.conditional()?;
+
+            return Ok(Conditional {
+                condition: Box::new(condition),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            });
+        }
+
+        Ok(condition)
+    }
+
     pub fn or(&mut self) -> Result<Expression, String> {
         let mut expression = self.and()?;
 
@@ -358,12 +868,12 @@ impl<'a> Parser<'a> {
     }
 
     fn equality(&mut self) -> Result<Expression, String> {
-        let mut expression: Expression = self.comparison()?;
+        let mut expression: Expression = self.bitwise_or()?;
 
         while self.match_tokens(vec![BangEqual, EqualEqual]) {
             let operator = self.previous();
 
-            let right: Expression = self.comparison()?;
+            let right: Expression = self.bitwise_or()?;
 
             expression = Binary {
                 left: Box::new(expression),
@@ -375,13 +885,64 @@ impl<'a> Parser<'a> {
         Ok(expression)
     }
 
+    fn bitwise_or(&mut self) -> Result<Expression, String> {
+        let mut expression = self.bitwise_xor()?;
+
+        while self.match_tokens(vec![Pipe]) {
+            let operator = self.previous();
+            let right = self.bitwise_xor()?;
+
+            expression = Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    fn bitwise_xor(&mut self) -> Result<Expression, String> {
+        let mut expression = self.bitwise_and()?;
+
+        while self.match_tokens(vec![Caret]) {
+            let operator = self.previous();
+            let right = self.bitwise_and()?;
+
+            expression = Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
+    fn bitwise_and(&mut self) -> Result<Expression, String> {
+        let mut expression = self.comparison()?;
+
+        while self.match_tokens(vec![Ampersand]) {
+            let operator = self.previous();
+            let right = self.comparison()?;
+
+            expression = Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
     fn comparison(&mut self) -> Result<Expression, String> {
-        let mut expression: Expression = self.term()?;
+        let mut expression: Expression = self.range()?;
 
         while self.match_tokens(vec![Greater, GreaterEqual, Less, LessEqual]) {
             let operator = self.previous();
 
-            let right = self.term()?;
+            let right = self.range()?;
 
             expression = Binary {
                 left: Box::new(expression),
@@ -393,6 +954,56 @@ impl<'a> Parser<'a> {
         Ok(expression)
     }
 
+    fn range(&mut self) -> Result<Expression, String> {
+        let start = self.shift()?;
+
+        if !self.match_tokens(vec![DotDot, DotDotEqual]) {
+            return Ok(start);
+        }
+
+        let operator = self.previous();
+        let end = self.shift()?;
+
+        let mut step = None;
+        if self.match_step() {
+            step = Some(Box::new(self.shift()?));
+        }
+
+        Ok(Expression::Range {
+            start: Box::new(start),
+            operator,
+            end: Box::new(end),
+            step,
+        })
+    }
+
+    // 'step' is only a keyword right after a range, so it stays usable as a name
+    fn match_step(&mut self) -> bool {
+        if self.check(Identifier) && self.peek().lexeme == "step" {
+            self.advance();
+            return true;
+        }
+
+        false
+    }
+
+    fn shift(&mut self) -> Result<Expression, String> {
+        let mut expression = self.term()?;
+
+        while self.match_tokens(vec![LessLess, GreaterGreater]) {
+            let operator = self.previous();
+            let right = self.term()?;
+
+            expression = Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            }
+        }
+
+        Ok(expression)
+    }
+
     fn term(&mut self) -> Result<Expression, String> {
         let mut expression = self.factor()?;
 
@@ -414,7 +1025,7 @@ impl<'a> Parser<'a> {
     fn factor(&mut self) -> Result<Expression, String> {
         let mut expression = self.unary()?;
 
-        while self.match_tokens(vec![Slash, Star]) {
+        while self.match_tokens(vec![Slash, Star, Percent, SlashSlash]) {
             let operator = self.previous();
             let right = self.unary()?;
 
@@ -429,7 +1040,22 @@ impl<'a> Parser<'a> {
     }
 
     fn unary(&mut self) -> Result<Expression, String> {
-        if self.match_tokens(vec![Bang, Minus]) {
+        if self.match_tokens(vec![PlusPlus, MinusMinus]) {
+            let operator = self.previous();
+            let target = self.unary()?;
+            let one = Literal {
+                value: LiteralValue::IntValue(1),
+            };
+
+            return Self::assignment_target(
+                target,
+                one,
+                Some(Self::compound_operator(&operator)),
+                &operator,
+            );
+        }
+
+        if self.match_tokens(vec![Bang, Minus, Tilde]) {
             let operator = self.previous();
             let right = self.unary()?;
 
@@ -439,7 +1065,43 @@ impl<'a> Parser<'a> {
             });
         }
 
-        self.call()
+        self.exponent()
+    }
+
+    // binds tighter than a unary minus on its left, right associative
+    fn exponent(&mut self) -> Result<Expression, String> {
+        let expression = self.postfix()?;
+
+        if self.match_tokens(vec![StarStar]) {
+            let operator = self.previous();
+            let right = self.unary()?;
+
+            return Ok(Binary {
+                left: Box::new(expression),
+                operator,
+                right: Box::new(right),
+            });
+        }
+
+        Ok(expression)
+    }
+
+    fn postfix(&mut self) -> Result<Expression, String> {
+        let expression = self.call()?;
+
+        if self.match_tokens(vec![PlusPlus, MinusMinus]) {
+            let operator = self.previous();
+
+            return match expression {
+                Variable { .. } | Get { .. } | Index { .. } => Ok(Postfix {
+                    target: Box::new(expression),
+                    operator,
+                }),
+                _ => Err(format!("Invalid {} target {}", operator.lexeme, expression)),
+            };
+        }
+
+        Ok(expression)
     }
 
     fn call(&mut self) -> Result<Expression, String> {
@@ -448,6 +1110,20 @@ impl<'a> Parser<'a> {
         loop {
             if self.match_tokens(vec![LeftParen]) {
                 expr = self.finish_call(expr)?;
+            } else if self.match_tokens(vec![Dot]) {
+                let name = self.consume(Identifier, "Expect property name after '.'")?;
+                expr = Get {
+                    object: Box::new(expr),
+                    name,
+                };
+            } else if self.match_tokens(vec![LeftBracket]) {
+                let index = self.expression()?;
+                let bracket = self.consume(RightBracket, "Expect ']' after index")?;
+                expr = Index {
+                    object: Box::new(expr),
+                    bracket,
+                    index: Box::new(index),
+                };
             } else {
                 break;
             }
@@ -458,14 +1134,26 @@ impl<'a> Parser<'a> {
 
     fn finish_call(&mut self, callee: Expression) -> Result<Expression, String> {
         let mut arguments = vec![];
+        let mut named: Vec<(Token, Expression)> = vec![];
 
         if !self.check(RightParen) {
             loop {
-                if arguments.len() >= 255 {
+                if arguments.len() + named.len() >= 255 {
                     return Err(String::from("Can't have more than 255 arguments"));
                 }
 
-                arguments.push(self.expression()?);
+                if self.check(Identifier) && self.check_next(Colon) {
+                    let name = self.advance();
+                    self.advance();
+                    named.push((name, self.expression()?));
+                } else if let Some((name, _)) = named.last() {
+                    return Err(format!(
+                        "Positional argument after named argument {} at line {}",
+                        name.lexeme, name.line
+                    ));
+                } else {
+                    arguments.push(self.expression()?);
+                }
 
                 if !self.match_tokens(vec![Comma]) {
                     break;
@@ -479,6 +1167,7 @@ impl<'a> Parser<'a> {
             callee: Box::new(callee),
             paren: right_paren,
             arguments: arguments,
+            named,
         })
     }
 
@@ -500,12 +1189,44 @@ impl<'a> Parser<'a> {
             });
         }
 
+        if self.match_tokens(vec![Fun]) {
+            return self.lambda();
+        }
+
+        if self.match_tokens(vec![TokenType::Super]) {
+            let keyword = self.previous();
+            self.consume(Dot, "Expect '.' after 'super'")?;
+            let method = self.consume(Identifier, "Expect superclass method name")?;
+
+            return Ok(Expression::Super {
+                keyword,
+                method,
+                depth: None,
+            });
+        }
+
+        if self.match_tokens(vec![TokenType::This]) {
+            return Ok(Expression::This {
+                keyword: self.previous(),
+                depth: None,
+            });
+        }
+
         if self.match_tokens(vec![Identifier]) {
             return Ok(Variable {
                 token: self.previous(),
+                depth: None,
             });
         }
 
+        if self.match_tokens(vec![LeftBracket]) {
+            return self.list();
+        }
+
+        if self.match_tokens(vec![LeftBrace]) {
+            return self.map();
+        }
+
         if self.match_tokens(vec![LeftParen]) {
             let expression = self.expression()?;
 
@@ -516,6 +1237,10 @@ impl<'a> Parser<'a> {
             });
         }
 
+        if self.match_tokens(vec![Interpolation]) {
+            return self.interpolation();
+        }
+
         if self.match_tokens(vec![String, Number]) {
             let token: Token = self.previous();
             return Ok(Literal {
@@ -530,6 +1255,100 @@ impl<'a> Parser<'a> {
         )))
     }
 
+    // "a${b}c" becomes ("a" + b) + "c", the leading string piece makes every + concatenate
+    fn interpolation(&mut self) -> Result<Expression, String> {
+        let line = self.previous().line;
+        let mut expression = Literal {
+            value: LiteralValue::from(self.previous()),
+        };
+
+        loop {
+            let piece = self.expression()?;
+            expression = Self::concatenate(expression, piece, line);
+
+            let right_brace =
+                self.consume(RightBrace, "Expected '}' after interpolated expression")?;
+
+            if !self.match_tokens(vec![Interpolation, String]) {
+                return Err(format!(
+                    "Unterminated string interpolation at line {}",
+                    right_brace.line
+                ));
+            }
+
+            let token = self.previous();
+            let is_last = token.token_type == String;
+
+            expression = Self::concatenate(
+                expression,
+                Literal {
+                    value: LiteralValue::from(token),
+                },
+                line,
+            );
+
+            if is_last {
+                return Ok(expression);
+            }
+        }
+    }
+
+    fn concatenate(left: Expression, right: Expression, line: usize) -> Expression {
+        Binary {
+            left: Box::new(left),
+            operator: Token::new(Plus, String::from("+"), None, line),
+            right: Box::new(right),
+        }
+    }
+
+    fn list(&mut self) -> Result<Expression, String> {
+        let mut elements = vec![];
+
+        if !self.check(RightBracket) {
+            loop {
+                elements.push(self.expression()?);
+
+                if !self.match_tokens(vec![Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(RightBracket, "Expected ']' after list elements")?;
+
+        Ok(List { elements })
+    }
+
+    fn map(&mut self) -> Result<Expression, String> {
+        let mut entries = vec![];
+
+        if !self.check(RightBrace) {
+            loop {
+                let key = self.expression()?;
+                self.consume(Colon, "Expected ':' after map key")?;
+                let value = self.expression()?;
+                entries.push((key, value));
+
+                if !self.match_tokens(vec![Comma]) {
+                    break;
+                }
+            }
+        }
+
+        self.consume(RightBrace, "Expected '}' after map entries")?;
+
+        Ok(Map { entries })
+    }
+
+    // at the start of a statement '{' opens a block unless it is followed by "key:"
+    fn is_map_literal(&self) -> bool {
+        self.check(LeftBrace)
+            && matches!(
+                self.tokens.get(self.current + 2),
+                Some(token) if token.token_type == Colon
+            )
+    }
+
     fn synchronize(&mut self) {
         self.advance();
 
@@ -538,7 +1357,8 @@ impl<'a> Parser<'a> {
                 return;
             }
             match self.peek().token_type {
-                Class | Fun | Var | For | If | While | Print | Return => return,
+                Class | Fun | Var | Const | For | If | Import | Match | Try | Throw | Yield
+                | While | Print | Return | Break | Continue => return,
                 _ => {}
             }
 
@@ -569,6 +1389,13 @@ impl<'a> Parser<'a> {
         self.peek().token_type == token_type
     }
 
+    fn check_next(&self, token_type: TokenType) -> bool {
+        match self.tokens.get(self.current + 1) {
+            Some(token) => token.token_type == token_type,
+            None => false,
+        }
+    }
+
     fn is_at_end(&self) -> bool {
         self.peek().token_type == Eof
     }
@@ -0,0 +1,540 @@
+use crate::expression::Expression;
+use crate::statement::{Parameter, Statement};
+use crate::token::Token;
+
+use std::collections::HashMap;
+use std::string::String;
+
+#[cfg(test)]
+#[path = "./tests/resolver_tests.rs"]
+mod tests;
+
+#[derive(Clone, Copy, PartialEq)]
+enum FunctionKind {
+    None,
+    Function,
+    Method,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum ClassKind {
+    None,
+    Class,
+    Subclass,
+}
+
+pub struct Resolver {
+    scopes: Vec<HashMap<String, bool>>,
+    // constants of each scope with the line they are declared at
+    constants: Vec<HashMap<String, usize>>,
+    // constants of the global scope, ones from earlier runs are checked by the environment
+    global_constants: HashMap<String, usize>,
+    current_function: FunctionKind,
+    current_class: ClassKind,
+}
+
+impl Resolver {
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![],
+            constants: vec![],
+            global_constants: HashMap::new(),
+            current_function: FunctionKind::None,
+            current_class: ClassKind::None,
+        }
+    }
+
+    pub fn resolve(&mut self, statements: &mut [Statement]) -> Result<(), String> {
+        let mut errors = vec![];
+
+        for statement in statements.iter_mut() {
+            if let Err(message) = self.resolve_statement(statement) {
+                errors.push(message);
+
+                // an error can leave us in the middle of a scope, start the next one clean
+                self.scopes.clear();
+                self.constants.clear();
+                self.current_function = FunctionKind::None;
+                self.current_class = ClassKind::None;
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
+
+        Ok(())
+    }
+
+    // region statements
+
+    fn resolve_statements(&mut self, statements: &mut [Statement]) -> Result<(), String> {
+        for statement in statements.iter_mut() {
+            self.resolve_statement(statement)?;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_statement(&mut self, statement: &mut Statement) -> Result<(), String> {
+        match statement {
+            Statement::Expression { expression } | Statement::Print { expression } => {
+                self.resolve_expression(expression)
+            }
+            Statement::Variable {
+                token, initializer, ..
+            } => {
+                self.declare(token)?;
+                self.resolve_expression(initializer)?;
+                self.define(token);
+                Ok(())
+            }
+            Statement::Const {
+                token, initializer, ..
+            } => {
+                self.declare(token)?;
+                self.resolve_expression(initializer)?;
+                self.define(token);
+
+                match self.constants.last_mut() {
+                    Some(constants) => constants.insert(String::from(&token.lexeme), token.line),
+                    None => self
+                        .global_constants
+                        .insert(String::from(&token.lexeme), token.line),
+                };
+
+                Ok(())
+            }
+            Statement::Block { statements } => {
+                self.begin_scope();
+                let result = self.resolve_statements(statements);
+                self.end_scope();
+                result
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expression(condition)?;
+                self.resolve_statement(then_branch)?;
+
+                if let Some(else_branch) = else_branch {
+                    self.resolve_statement(else_branch)?;
+                }
+
+                Ok(())
+            }
+            Statement::While {
+                condition,
+                body,
+                increment,
+            } => {
+                self.resolve_expression(condition)?;
+                self.resolve_statement(body)?;
+
+                match increment {
+                    Some(increment) => self.resolve_expression(increment),
+                    None => Ok(()),
+                }
+            }
+            Statement::Function {
+                name,
+                parameters,
+                body,
+                ..
+            } => {
+                self.declare(name)?;
+                self.define(name);
+
+                self.resolve_function(parameters, body, FunctionKind::Function)
+            }
+            Statement::Class {
+                name,
+                superclass,
+                methods,
+            } => {
+                let enclosing_class = self.current_class;
+                self.current_class = ClassKind::Class;
+
+                self.declare(name)?;
+                self.define(name);
+
+                if let Some(superclass) = superclass {
+                    self.current_class = ClassKind::Subclass;
+                    self.resolve_expression(superclass)?;
+
+                    self.begin_scope();
+                    self.define_name("super");
+                }
+
+                self.begin_scope();
+                self.define_name("this");
+
+                for method in methods.iter_mut() {
+                    if let Statement::Function {
+                        parameters, body, ..
+                    } = method
+                    {
+                        self.resolve_function(parameters, body, FunctionKind::Method)?;
+                    }
+                }
+
+                self.end_scope();
+
+                if superclass.is_some() {
+                    self.end_scope();
+                }
+
+                self.current_class = enclosing_class;
+
+                Ok(())
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+            } => {
+                self.resolve_expression(iterable)?;
+
+                self.begin_scope();
+                self.define(variable);
+                let result = self.resolve_statement(body);
+                self.end_scope();
+
+                result
+            }
+            Statement::Return { keyword, value } => {
+                if self.current_function == FunctionKind::None {
+                    return Err(format!(
+                        "Can't return from top-level code at line {}",
+                        keyword.line
+                    ));
+                }
+
+                match value {
+                    Some(value) => self.resolve_expression(value),
+                    None => Ok(()),
+                }
+            }
+            Statement::Match { subject, arms, .. } => {
+                self.resolve_expression(subject)?;
+
+                for (_, body) in arms.iter_mut() {
+                    self.resolve_statement(body)?;
+                }
+
+                Ok(())
+            }
+            Statement::Import { keyword, name, .. } => {
+                // paths resolve against the file being run, which is only known at the top level
+                if !self.scopes.is_empty() || self.current_function != FunctionKind::None {
+                    return Err(format!(
+                        "Can't import outside of top-level code at line {}",
+                        keyword.line
+                    ));
+                }
+
+                self.declare(name)?;
+                self.define(name);
+                Ok(())
+            }
+            Statement::Yield { keyword, value } => {
+                if self.current_function == FunctionKind::None {
+                    return Err(format!(
+                        "Can't yield from top-level code at line {}",
+                        keyword.line
+                    ));
+                }
+
+                match value {
+                    Some(value) => self.resolve_expression(value),
+                    None => Ok(()),
+                }
+            }
+            Statement::Throw { value, .. } => self.resolve_expression(value),
+            Statement::Try {
+                body,
+                catch,
+                finally,
+                ..
+            } => {
+                self.begin_scope();
+                let result = self.resolve_statements(body);
+                self.end_scope();
+                result?;
+
+                if let Some((name, statements)) = catch {
+                    self.begin_scope();
+                    self.define(name);
+                    let result = self.resolve_statements(statements);
+                    self.end_scope();
+                    result?;
+                }
+
+                if let Some(statements) = finally {
+                    self.begin_scope();
+                    let result = self.resolve_statements(statements);
+                    self.end_scope();
+                    result?;
+                }
+
+                Ok(())
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => Ok(()),
+        }
+    }
+
+    fn resolve_function(
+        &mut self,
+        parameters: &mut [Parameter],
+        body: &mut [Statement],
+        kind: FunctionKind,
+    ) -> Result<(), String> {
+        let enclosing_function = self.current_function;
+        self.current_function = kind;
+
+        self.begin_scope();
+
+        // defaults are evaluated on call, after the parameters before them are bound
+        for parameter in parameters.iter_mut() {
+            if let Some(default) = &mut parameter.default {
+                self.resolve_expression(default)?;
+            }
+
+            self.declare(&parameter.name)?;
+            self.define(&parameter.name);
+        }
+
+        self.resolve_statements(body)?;
+
+        self.end_scope();
+
+        self.current_function = enclosing_function;
+
+        Ok(())
+    }
+
+    // endregion
+
+    // region expressions
+
+    fn resolve_expression(&mut self, expression: &mut Expression) -> Result<(), String> {
+        match expression {
+            Expression::Literal { .. } => Ok(()),
+            Expression::Grouping { group } => self.resolve_expression(group),
+            Expression::Unary { right, .. } => self.resolve_expression(right),
+            Expression::Binary { left, right, .. } | Expression::Logical { left, right, .. } => {
+                self.resolve_expression(left)?;
+                self.resolve_expression(right)
+            }
+            Expression::Variable { token, depth } => {
+                if let Some(scope) = self.scopes.last() {
+                    if scope.get(&token.lexeme) == Some(&false) {
+                        return Err(format!(
+                            "Can't read local variable {} in its own initializer at line {}",
+                            token.lexeme, token.line
+                        ));
+                    }
+                }
+
+                *depth = Some(self.resolve_local(&token.lexeme));
+                Ok(())
+            }
+            Expression::Assign { name, value, depth } => {
+                self.resolve_expression(value)?;
+
+                let distance = self.resolve_local(&name.lexeme);
+                self.check_constant(&name.lexeme, distance)?;
+
+                *depth = Some(distance);
+                Ok(())
+            }
+            Expression::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.resolve_expression(condition)?;
+                self.resolve_expression(then_branch)?;
+                self.resolve_expression(else_branch)
+            }
+            Expression::Call {
+                callee,
+                arguments,
+                named,
+                ..
+            } => {
+                self.resolve_expression(callee)?;
+
+                for argument in arguments.iter_mut() {
+                    self.resolve_expression(argument)?;
+                }
+
+                for (_, argument) in named.iter_mut() {
+                    self.resolve_expression(argument)?;
+                }
+
+                Ok(())
+            }
+            Expression::Get { object, .. } => self.resolve_expression(object),
+            Expression::Postfix { target, .. } => {
+                self.resolve_expression(target)?;
+
+                match target.as_ref() {
+                    Expression::Variable {
+                        token,
+                        depth: Some(distance),
+                    } => self.check_constant(&token.lexeme, *distance),
+                    _ => Ok(()),
+                }
+            }
+            Expression::Set { object, value, .. } => {
+                self.resolve_expression(value)?;
+                self.resolve_expression(object)
+            }
+            Expression::List { elements } => {
+                for element in elements.iter_mut() {
+                    self.resolve_expression(element)?;
+                }
+
+                Ok(())
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries.iter_mut() {
+                    self.resolve_expression(key)?;
+                    self.resolve_expression(value)?;
+                }
+
+                Ok(())
+            }
+            Expression::Index { object, index, .. } => {
+                self.resolve_expression(object)?;
+                self.resolve_expression(index)
+            }
+            Expression::Range {
+                start, end, step, ..
+            } => {
+                self.resolve_expression(start)?;
+                self.resolve_expression(end)?;
+
+                match step {
+                    Some(step) => self.resolve_expression(step),
+                    None => Ok(()),
+                }
+            }
+            Expression::SetIndex {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                self.resolve_expression(value)?;
+                self.resolve_expression(object)?;
+                self.resolve_expression(index)
+            }
+            Expression::This { keyword, depth } => {
+                if self.current_class == ClassKind::None {
+                    return Err(format!(
+                        "Can't use 'this' outside of a class at line {}",
+                        keyword.line
+                    ));
+                }
+
+                *depth = Some(self.resolve_local(&keyword.lexeme));
+                Ok(())
+            }
+            Expression::Super { keyword, depth, .. } => {
+                match self.current_class {
+                    ClassKind::None => {
+                        return Err(format!(
+                            "Can't use 'super' outside of a class at line {}",
+                            keyword.line
+                        ))
+                    }
+                    ClassKind::Class => {
+                        return Err(format!(
+                            "Can't use 'super' in a class with no superclass at line {}",
+                            keyword.line
+                        ))
+                    }
+                    ClassKind::Subclass => {}
+                }
+
+                *depth = Some(self.resolve_local(&keyword.lexeme));
+                Ok(())
+            }
+            Expression::Lambda {
+                parameters, body, ..
+            } => self.resolve_function(parameters, body, FunctionKind::Function),
+        }
+    }
+
+    // endregion
+
+    // region helper functions
+
+    fn begin_scope(&mut self) {
+        self.scopes.push(HashMap::new());
+        self.constants.push(HashMap::new());
+    }
+
+    fn end_scope(&mut self) {
+        self.scopes.pop();
+        self.constants.pop();
+    }
+
+    fn declare(&mut self, name: &Token) -> Result<(), String> {
+        if let Some(scope) = self.scopes.last_mut() {
+            if scope.contains_key(&name.lexeme) {
+                return Err(format!(
+                    "Already a variable with name {} in this scope at line {}",
+                    name.lexeme, name.line
+                ));
+            }
+
+            scope.insert(String::from(&name.lexeme), false);
+        }
+
+        Ok(())
+    }
+
+    fn define(&mut self, name: &Token) {
+        self.define_name(&name.lexeme);
+    }
+
+    fn define_name(&mut self, name: &str) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(String::from(name), true);
+        }
+    }
+
+    fn check_constant(&self, name: &str, distance: usize) -> Result<(), String> {
+        let line = if distance < self.constants.len() {
+            self.constants[self.constants.len() - 1 - distance].get(name)
+        } else {
+            self.global_constants.get(name)
+        };
+
+        match line {
+            Some(line) => Err(format!(
+                "Can't reassign constant {} declared at line {}",
+                name, line
+            )),
+            None => Ok(()),
+        }
+    }
+
+    // names not found in any local scope live in the global environment
+    fn resolve_local(&self, name: &str) -> usize {
+        for (i, scope) in self.scopes.iter().enumerate().rev() {
+            if scope.contains_key(name) {
+                return self.scopes.len() - 1 - i;
+            }
+        }
+
+        self.scopes.len()
+    }
+
+    // endregion
+}
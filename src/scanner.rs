@@ -16,6 +16,8 @@ pub struct Scanner<'a> {
     current: usize,
     line: usize,
     keywords: HashMap<&'a str, TokenType>,
+    // open braces inside of each unfinished string interpolation
+    interpolations: Vec<usize>,
 }
 
 impl<'a> Scanner<'a> {
@@ -23,21 +25,32 @@ impl<'a> Scanner<'a> {
         let mut keywords: HashMap<&str, TokenType> = HashMap::new();
 
         keywords.insert("and", And);
+        keywords.insert("break", Break);
+        keywords.insert("catch", Catch);
         keywords.insert("class", Class);
+        keywords.insert("const", Const);
+        keywords.insert("continue", Continue);
         keywords.insert("else", Else);
         keywords.insert("false", False);
+        keywords.insert("finally", Finally);
         keywords.insert("for", For);
         keywords.insert("fun", Fun);
         keywords.insert("if", If);
+        keywords.insert("import", Import);
+        keywords.insert("in", In);
+        keywords.insert("match", Match);
         keywords.insert("nil", Nil);
         keywords.insert("or", Or);
         keywords.insert("print", Print);
         keywords.insert("return", Return);
         keywords.insert("super", Super);
         keywords.insert("this", This);
+        keywords.insert("throw", Throw);
         keywords.insert("true", True);
+        keywords.insert("try", Try);
         keywords.insert("var", Var);
         keywords.insert("while", While);
+        keywords.insert("yield", Yield);
 
         Self {
             source: source,
@@ -46,6 +59,7 @@ impl<'a> Scanner<'a> {
             current: 0,
             line: 1,
             keywords: keywords,
+            interpolations: vec![],
         }
     }
 
@@ -59,6 +73,13 @@ impl<'a> Scanner<'a> {
             }
         }
 
+        if !self.interpolations.is_empty() {
+            errors.push(format!(
+                "Unterminated string interpolation at line {0}",
+                self.line
+            ));
+        }
+
         self.tokens
             .push(Token::new(Eof, "".to_string(), None, self.line));
 
@@ -76,13 +97,69 @@ impl<'a> Scanner<'a> {
         match symbol {
             '(' => self.add_token(LeftParen),
             ')' => self.add_token(RightParen),
-            '{' => self.add_token(LeftBrace),
-            '}' => self.add_token(RightBrace),
+            '{' => {
+                if let Some(depth) = self.interpolations.last_mut() {
+                    *depth += 1;
+                }
+                self.add_token(LeftBrace)
+            }
+            '}' => {
+                self.add_token(RightBrace);
+
+                match self.interpolations.last_mut() {
+                    Some(0) => {
+                        self.interpolations.pop();
+                        self.start = self.current;
+                        self.string()?;
+                    }
+                    Some(depth) => *depth -= 1,
+                    None => {}
+                }
+            }
+            '[' => self.add_token(LeftBracket),
+            ']' => self.add_token(RightBracket),
             ',' => self.add_token(Comma),
-            '.' => self.add_token(Dot),
-            '-' => self.add_token(Minus),
-            '+' => self.add_token(Plus),
+            ':' => self.add_token(Colon),
+            '?' => self.add_token(Question),
+            '.' => {
+                if self.match_character('.') {
+                    if self.match_character('=') {
+                        self.add_token(DotDotEqual)
+                    } else if self.match_character('.') {
+                        self.add_token(DotDotDot)
+                    } else {
+                        self.add_token(DotDot)
+                    }
+                } else {
+                    self.add_token(Dot)
+                }
+            }
+            '-' => {
+                if self.match_character('-') {
+                    self.add_token(MinusMinus)
+                } else if self.match_character('=') {
+                    self.add_token(MinusEqual)
+                } else if self.match_character('>') {
+                    self.add_token(Arrow)
+                } else {
+                    self.add_token(Minus)
+                }
+            }
+            '+' => {
+                if self.match_character('+') {
+                    self.add_token(PlusPlus)
+                } else if self.match_character('=') {
+                    self.add_token(PlusEqual)
+                } else {
+                    self.add_token(Plus)
+                }
+            }
             ';' => self.add_token(Semicolon),
+            '%' => self.add_token(Percent),
+            '&' => self.add_token(Ampersand),
+            '|' => self.add_token(Pipe),
+            '^' => self.add_token(Caret),
+            '~' => self.add_token(Tilde),
             '*' => {
                 if self.match_character('/') {
                     return Err(format!(
@@ -90,7 +167,14 @@ impl<'a> Scanner<'a> {
                         self.line
                     ));
                 }
-                self.add_token(Star)
+
+                if self.match_character('*') {
+                    self.add_token(StarStar)
+                } else if self.match_character('=') {
+                    self.add_token(StarEqual)
+                } else {
+                    self.add_token(Star)
+                }
             }
             '!' => {
                 if self.match_character('=') {
@@ -102,6 +186,8 @@ impl<'a> Scanner<'a> {
             '=' => {
                 if self.match_character('=') {
                     self.add_token(EqualEqual)
+                } else if self.match_character('>') {
+                    self.add_token(FatArrow)
                 } else {
                     self.add_token(Equal)
                 }
@@ -109,6 +195,8 @@ impl<'a> Scanner<'a> {
             '<' => {
                 if self.match_character('=') {
                     self.add_token(LessEqual)
+                } else if self.match_character('<') {
+                    self.add_token(LessLess)
                 } else {
                     self.add_token(Less)
                 }
@@ -116,12 +204,18 @@ impl<'a> Scanner<'a> {
             '>' => {
                 if self.match_character('=') {
                     self.add_token(GreaterEqual)
+                } else if self.match_character('>') {
+                    self.add_token(GreaterGreater)
                 } else {
                     self.add_token(Greater)
                 }
             }
             '/' => {
-                if self.match_character('/') {
+                // "//" written right after an operand is integer division, anywhere else a comment
+                if self.peek() == '/' && self.directly_follows_operand() {
+                    self.advance();
+                    self.add_token(SlashSlash)
+                } else if self.match_character('/') {
                     // A comment goes until the end of the line.
                     while self.peek() != '\n' && !self.is_at_end() {
                         self.advance();
@@ -146,6 +240,8 @@ impl<'a> Scanner<'a> {
                             self.advance();
                         }
                     }
+                } else if self.match_character('=') {
+                    self.add_token(SlashEqual);
                 } else {
                     self.add_token(Slash);
                 }
@@ -183,28 +279,119 @@ impl<'a> Scanner<'a> {
     // region parser function
 
     fn string(&mut self) -> Result<(), String> {
+        let mut value = String::new();
+        let mut errors = vec![];
+
         while self.peek() != '"' && !self.is_at_end() {
-            if self.peek() == '\n' {
-                self.line += 1;
+            if self.peek() == '$' && self.peek_next() == '{' {
+                self.advance();
+                self.advance();
+
+                // the expression tokens follow, the string resumes after the matching '}'
+                self.interpolations.push(0);
+
+                if !errors.is_empty() {
+                    return Err(errors.join("\n"));
+                }
+
+                self.add_token_lit(Interpolation, Some(StringValue(value)));
+
+                return Ok(());
             }
 
-            self.advance();
+            let symbol = self.advance();
+
+            match symbol {
+                '\\' => match self.escape_sequence() {
+                    Ok(character) => value.push(character),
+                    // keep going so that the rest of the string is not scanned as code
+                    Err(message) => errors.push(message),
+                },
+                '\n' => {
+                    self.line += 1;
+                    value.push(symbol);
+                }
+                _ => value.push(symbol),
+            }
         }
 
         if self.is_at_end() {
-            return Err(format!("Unterminated string at line {0}", self.line));
+            errors.push(format!("Unterminated string at line {0}", self.line));
+            return Err(errors.join("\n"));
         }
 
         self.advance();
 
-        // rust ranges are inclusive
-        let value = &self.source[self.start + 1..self.current - 1];
+        if !errors.is_empty() {
+            return Err(errors.join("\n"));
+        }
 
-        self.add_token_lit(String, Some(StringValue(value.to_string())));
+        self.add_token_lit(String, Some(StringValue(value)));
 
         Ok(())
     }
 
+    fn escape_sequence(&mut self) -> Result<char, String> {
+        if self.is_at_end() {
+            return Err(format!(
+                "Unterminated escape sequence at line {0}",
+                self.line
+            ));
+        }
+
+        let symbol = self.advance();
+
+        match symbol {
+            'n' => Ok('\n'),
+            't' => Ok('\t'),
+            'r' => Ok('\r'),
+            '0' => Ok('\0'),
+            '"' => Ok('"'),
+            '$' => Ok('$'),
+            '\\' => Ok('\\'),
+            'u' => self.unicode_escape(),
+            '\n' => {
+                self.line += 1;
+                Err(format!(
+                    "Invalid escape sequence '\\' at end of line {0}",
+                    self.line - 1
+                ))
+            }
+            _ => Err(format!(
+                "Invalid escape sequence '\\{0}' at line {1}",
+                symbol, self.line
+            )),
+        }
+    }
+
+    fn unicode_escape(&mut self) -> Result<char, String> {
+        if !self.match_character('{') {
+            return Err(format!("Expected '{{' after '\\u' at line {0}", self.line));
+        }
+
+        let mut digits = String::new();
+
+        while self.peek().is_ascii_hexdigit() {
+            digits.push(self.advance());
+        }
+
+        if !self.match_character('}') {
+            return Err(format!(
+                "Unterminated unicode escape '\\u{{{0}' at line {1}",
+                digits, self.line
+            ));
+        }
+
+        u32::from_str_radix(&digits, 16)
+            .ok()
+            .filter(|_| digits.len() <= 6)
+            .and_then(char::from_u32)
+            .ok_or(format!(
+                "Invalid unicode escape '\\u{{{0}}}' at line {1}",
+                digits, self.line
+            ))
+    }
+
     fn number(&mut self) -> Result<(), String> {
         let mut is_fraction = false;
 
@@ -260,25 +447,19 @@ impl<'a> Scanner<'a> {
         self.current >= self.source.len()
     }
 
+    // current is a byte offset, so step over whole utf-8 characters
     fn advance(&mut self) -> char {
-        let symbol = self.source.as_bytes()[self.current];
-        self.current += 1;
-        symbol as char
+        let symbol = self.peek();
+        self.current += symbol.len_utf8();
+        symbol
     }
 
     fn peek(&self) -> char {
-        if self.is_at_end() {
-            return '\0';
-        }
-        self.source.as_bytes()[self.current] as char
+        self.source[self.current..].chars().next().unwrap_or('\0')
     }
 
     fn peek_next(&self) -> char {
-        if self.current + 1 >= self.source.len() {
-            return '\0';
-        }
-
-        self.source.as_bytes()[self.current + 1] as char
+        self.source[self.current..].chars().nth(1).unwrap_or('\0')
     }
 
     fn match_character(&mut self, character: char) -> bool {
@@ -286,11 +467,11 @@ impl<'a> Scanner<'a> {
             return false;
         }
 
-        if self.source.as_bytes()[self.current] as char != character {
+        if self.peek() != character {
             return false;
         }
 
-        self.current += 1;
+        self.current += character.len_utf8();
         true
     }
 
@@ -303,9 +484,33 @@ impl<'a> Scanner<'a> {
     }
 
     fn is_alpha(&self, character: char) -> bool {
-        (character >= 'a' && character <= 'z')
-            || (character >= 'A' && character <= 'Z')
-            || character == '_'
+        character.is_alphabetic() || character == '_'
+    }
+
+    fn directly_follows_operand(&self) -> bool {
+        let touches_previous =
+            self.source[..self.start]
+                .chars()
+                .next_back()
+                .is_some_and(|character| {
+                    self.is_alpha_numeric(character) || matches!(character, '"' | ')' | ']')
+                });
+
+        touches_previous
+            && matches!(
+                self.tokens.last().map(|token| &token.token_type),
+                Some(
+                    Identifier
+                        | String
+                        | Number
+                        | True
+                        | False
+                        | Nil
+                        | This
+                        | RightParen
+                        | RightBracket
+                )
+            )
     }
 
     fn is_alpha_numeric(&self, character: char) -> bool {
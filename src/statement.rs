@@ -1,6 +1,9 @@
 use crate::expression;
+use crate::expression_literal_value::LiteralValue::{self, IntValue};
 use crate::token::Token;
 
+use std::fmt::{Display, Formatter};
+
 #[derive(Clone, Debug, PartialEq)]
 pub enum Statement {
     Expression {
@@ -13,6 +16,13 @@ pub enum Statement {
 
     Variable {
         token: Token,
+        annotation: Option<Token>,
+        initializer: expression::Expression,
+    },
+
+    Const {
+        token: Token,
+        annotation: Option<Token>,
         initializer: expression::Expression,
     },
 
@@ -29,16 +39,172 @@ pub enum Statement {
     While {
         condition: expression::Expression,
         body: Box<Statement>,
+        increment: Option<expression::Expression>,
+    },
+
+    ForIn {
+        variable: Token,
+        iterable: expression::Expression,
+        body: Box<Statement>,
     },
 
     Function {
         name: Token,
-        parameters: Vec<Token>,
+        parameters: Vec<Parameter>,
+        return_type: Option<Token>,
         body: Vec<Statement>,
     },
 
+    Class {
+        name: Token,
+        superclass: Option<expression::Expression>,
+        methods: Vec<Statement>,
+    },
+
     Return {
         keyword: Token,
         value: Option<expression::Expression>,
     },
+
+    Break {
+        keyword: Token,
+    },
+
+    Continue {
+        keyword: Token,
+    },
+
+    Match {
+        keyword: Token,
+        subject: expression::Expression,
+        arms: Vec<(Vec<Pattern>, Statement)>,
+    },
+
+    Import {
+        keyword: Token,
+        path: Token,
+        name: Token,
+    },
+
+    Yield {
+        keyword: Token,
+        value: Option<expression::Expression>,
+    },
+
+    Throw {
+        keyword: Token,
+        value: expression::Expression,
+    },
+
+    Try {
+        keyword: Token,
+        body: Vec<Statement>,
+        catch: Option<(Token, Vec<Statement>)>,
+        finally: Option<Vec<Statement>>,
+    },
+}
+
+impl Statement {
+    // yields inside of nested functions belong to those functions
+    pub fn contains_yield(&self) -> bool {
+        match self {
+            Statement::Yield { .. } => true,
+            Statement::Block { statements } => statements.iter().any(Statement::contains_yield),
+            Statement::If {
+                then_branch,
+                else_branch,
+                ..
+            } => {
+                then_branch.contains_yield()
+                    || else_branch
+                        .as_ref()
+                        .is_some_and(|else_branch| else_branch.contains_yield())
+            }
+            Statement::While { body, .. } | Statement::ForIn { body, .. } => body.contains_yield(),
+            Statement::Match { arms, .. } => arms.iter().any(|(_, body)| body.contains_yield()),
+            Statement::Try {
+                body,
+                catch,
+                finally,
+                ..
+            } => {
+                body.iter().any(Statement::contains_yield)
+                    || catch
+                        .as_ref()
+                        .is_some_and(|(_, body)| body.iter().any(Statement::contains_yield))
+                    || finally
+                        .as_ref()
+                        .is_some_and(|body| body.iter().any(Statement::contains_yield))
+            }
+            _ => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Parameter {
+    pub name: Token,
+    pub annotation: Option<Token>,
+    pub default: Option<expression::Expression>,
+    // collects the remaining arguments into a list
+    pub rest: bool,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match (&self.default, self.rest) {
+            (_, true) => write!(f, "...{}", self.name.lexeme),
+            (Some(default), false) => write!(f, "{}={}", self.name.lexeme, default),
+            (None, false) => write!(f, "{}", self.name.lexeme),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Pattern {
+    Literal(LiteralValue),
+    Range {
+        start: LiteralValue,
+        end: LiteralValue,
+        inclusive: bool,
+        // only set for Int bounds
+        step: Option<i64>,
+    },
+    Wildcard,
+}
+
+impl Pattern {
+    pub fn matches(&self, value: &LiteralValue) -> bool {
+        match self {
+            Pattern::Literal(literal) => value.is_equal(literal),
+            Pattern::Range {
+                start: IntValue(start),
+                end: IntValue(end),
+                inclusive,
+                step: Some(step),
+            } => match value {
+                IntValue(number) => LiteralValue::RangeValue {
+                    start: *start,
+                    end: *end,
+                    step: *step,
+                    inclusive: *inclusive,
+                }
+                .range_contains(*number),
+                _ => false,
+            },
+            Pattern::Range {
+                start,
+                end,
+                inclusive,
+                ..
+            } => match (start.to_number(), end.to_number(), value.to_number()) {
+                (Some(start), Some(end), Some(value)) if *inclusive => {
+                    start <= value && value <= end
+                }
+                (Some(start), Some(end), Some(value)) => start <= value && value < end,
+                _ => false,
+            },
+            Pattern::Wildcard => true,
+        }
+    }
 }
@@ -9,7 +9,7 @@ mod tests {
     #[test]
     fn define_add_value_value_is_saved() {
         let mut environment = Environment::new();
-        environment.define(String::from("test"), Nil);
+        environment.define(String::from("test"), Nil).unwrap();
 
         let variable = environment.get("test");
 
@@ -34,7 +34,8 @@ mod tests {
         let environment_enclosing = Rc::new(RefCell::new(Environment::new()));
         environment_enclosing
             .borrow_mut()
-            .define(String::from("test"), True);
+            .define(String::from("test"), True)
+            .unwrap();
 
         environment.enclosing = Some(environment_enclosing.clone());
 
@@ -52,7 +53,8 @@ mod tests {
 
         environment_parent
             .borrow_mut()
-            .define(String::from("test"), True);
+            .define(String::from("test"), True)
+            .unwrap();
 
         environment_child.enclosing = Some(environment_parent.clone());
 
@@ -81,11 +83,96 @@ mod tests {
     fn assign_to_created_value_returns_ok() {
         let mut environment = Environment::new();
 
-        environment.define(String::from("test"), Nil);
+        environment.define(String::from("test"), Nil).unwrap();
         let result = environment.assign(String::from("test"), False);
 
         assert!(result.is_ok());
 
         assert_eq!(environment.get("test"), Ok(False));
     }
+
+    #[test]
+    fn assign_to_constant_gets_error() {
+        let mut environment = Environment::new();
+        let environment_enclosing = Rc::new(RefCell::new(Environment::new()));
+        environment_enclosing
+            .borrow_mut()
+            .define_constant(String::from("test"), IntValue(1))
+            .unwrap();
+
+        environment.enclosing = Some(environment_enclosing.clone());
+
+        assert_eq!(
+            environment.assign(String::from("test"), IntValue(2)),
+            Err(String::from("Cannot assign to constant test"))
+        );
+        assert_eq!(
+            environment.assign_at(1, String::from("test"), IntValue(2)),
+            Err(String::from("Cannot assign to constant test"))
+        );
+        assert_eq!(environment.get("test"), Ok(IntValue(1)));
+    }
+
+    #[test]
+    fn define_over_constant_gets_error() {
+        let mut environment = Environment::new();
+        environment
+            .define_constant(String::from("test"), IntValue(1))
+            .unwrap();
+
+        assert_eq!(
+            environment.define(String::from("test"), IntValue(2)),
+            Err(String::from("Cannot redefine constant test"))
+        );
+        assert_eq!(
+            environment.define_constant(String::from("test"), IntValue(2)),
+            Err(String::from("Cannot redefine constant test"))
+        );
+        assert_eq!(environment.get("test"), Ok(IntValue(1)));
+    }
+
+    #[test]
+    fn define_shadows_constant_of_enclosing() {
+        let environment_enclosing = Rc::new(RefCell::new(Environment::new()));
+        environment_enclosing
+            .borrow_mut()
+            .define_constant(String::from("test"), IntValue(1))
+            .unwrap();
+
+        let mut environment = Environment::new();
+        environment.enclosing = Some(environment_enclosing.clone());
+
+        assert_eq!(
+            environment.define(String::from("test"), IntValue(2)),
+            Ok(())
+        );
+        assert_eq!(
+            environment.assign(String::from("test"), IntValue(3)),
+            Ok(())
+        );
+        assert_eq!(environment.get("test"), Ok(IntValue(3)));
+    }
+
+    #[test]
+    fn get_at_missing_name_is_looked_up_in_enclosing() {
+        let natives = Rc::new(RefCell::new(Environment::new()));
+        natives
+            .borrow_mut()
+            .define(String::from("clock"), IntValue(1))
+            .unwrap();
+
+        let mut module = Environment::new();
+        module.enclosing = Some(natives.clone());
+
+        assert_eq!(module.get_at(0, "clock"), Ok(IntValue(1)));
+        assert_eq!(
+            module.assign_at(0, String::from("clock"), IntValue(2)),
+            Ok(())
+        );
+        assert_eq!(natives.borrow().get("clock"), Ok(IntValue(2)));
+        assert_eq!(
+            module.get_at(0, "missing"),
+            Err(String::from("Undefined variable missing"))
+        );
+    }
 }
@@ -1,8 +1,10 @@
 #[cfg(test)]
 mod tests {
+    use crate::environment::Environment;
     use crate::expression_literal_value::LiteralValue;
     use crate::token::{Token, TokenType};
 
+    use std::cell::RefCell;
     use std::rc::Rc;
 
     #[test]
@@ -42,7 +44,10 @@ mod tests {
                 LiteralValue::Callable {
                     name: "my_func".to_string(),
                     arity: 2,
+                    max_arity: Some(2),
+                    parameters: Rc::new(vec![]),
                     fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
+                    environment: Rc::new(RefCell::new(Environment::new())),
                 },
                 "Callable { name: my_func, arity: 2 }",
             ),
@@ -55,6 +60,15 @@ mod tests {
 
     #[test]
     fn test_partial_eq() {
+        // callables are equal only when they are the same function
+        let callable = LiteralValue::Callable {
+            name: "my_func".to_string(),
+            arity: 2,
+            max_arity: Some(2),
+            parameters: Rc::new(vec![]),
+            fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
+            environment: Rc::new(RefCell::new(Environment::new())),
+        };
         let test_cases = vec![
             (LiteralValue::IntValue(42), LiteralValue::IntValue(42), true),
             (LiteralValue::FValue(3.14), LiteralValue::FValue(3.14), true),
@@ -71,30 +85,17 @@ mod tests {
             (LiteralValue::True, LiteralValue::True, true),
             (LiteralValue::False, LiteralValue::False, true),
             (LiteralValue::Nil, LiteralValue::Nil, true),
+            (callable.clone(), callable.clone(), true),
             (
                 LiteralValue::Callable {
                     name: "my_func".to_string(),
                     arity: 2,
+                    max_arity: Some(2),
+                    parameters: Rc::new(vec![]),
                     fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
+                    environment: Rc::new(RefCell::new(Environment::new())),
                 },
-                LiteralValue::Callable {
-                    name: "my_func".to_string(),
-                    arity: 2,
-                    fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
-                },
-                true,
-            ),
-            (
-                LiteralValue::Callable {
-                    name: "my_func".to_string(),
-                    arity: 2,
-                    fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
-                },
-                LiteralValue::Callable {
-                    name: "other_func".to_string(),
-                    arity: 2,
-                    fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
-                },
+                callable,
                 false,
             ),
         ];
@@ -116,7 +117,10 @@ mod tests {
             LiteralValue::Callable {
                 name: "other_func".to_string(),
                 arity: 2,
+                max_arity: Some(2),
+                parameters: Rc::new(vec![]),
                 fun: Rc::new(|_, _| Ok(LiteralValue::Nil)),
+                environment: Rc::new(RefCell::new(Environment::new())),
             },
         ];
 
@@ -130,6 +134,77 @@ mod tests {
         assert_eq!(result, responses);
     }
 
+    #[test]
+    fn range_value_display_and_type() {
+        let ranges = vec![
+            (
+                LiteralValue::RangeValue {
+                    start: 0,
+                    end: 10,
+                    step: 1,
+                    inclusive: false,
+                },
+                "0..10",
+            ),
+            (
+                LiteralValue::RangeValue {
+                    start: 10,
+                    end: 0,
+                    step: -2,
+                    inclusive: true,
+                },
+                "10..=0 step -2",
+            ),
+        ];
+
+        for (range, expected) in ranges {
+            assert_eq!(range.to_string(), expected);
+            assert_eq!(range.to_type(), "Range");
+        }
+    }
+
+    #[test]
+    fn range_value_numbers() {
+        let test_cases = vec![
+            ((0, 4, 1, false), vec![0, 1, 2, 3]),
+            ((0, 4, 2, true), vec![0, 2, 4]),
+            ((5, 0, -2, false), vec![5, 3, 1]),
+            ((3, 0, 1, false), vec![]),
+            (
+                (i64::MAX - 1, i64::MAX, 1, true),
+                vec![i64::MAX - 1, i64::MAX],
+            ),
+        ];
+
+        for ((start, end, step, inclusive), expected) in test_cases {
+            let range = LiteralValue::RangeValue {
+                start,
+                end,
+                step,
+                inclusive,
+            };
+
+            assert_eq!(range.range_numbers().collect::<Vec<i64>>(), expected);
+            assert_eq!(bool::from(&range), !expected.is_empty());
+        }
+    }
+
+    #[test]
+    fn range_value_contains() {
+        let range = LiteralValue::RangeValue {
+            start: 1,
+            end: 9,
+            step: 2,
+            inclusive: true,
+        };
+
+        assert!(range.range_contains(1));
+        assert!(range.range_contains(9));
+        assert!(!range.range_contains(4));
+        assert!(!range.range_contains(11));
+        assert!(!range.range_contains(-1));
+    }
+
     #[test]
     fn test_from_token() {
         let tokens = vec![
@@ -3,9 +3,10 @@ mod tests {
     use crate::environment::Environment;
     use crate::expression::Expression::{self, *};
     use crate::expression_literal_value::LiteralValue::{self, *};
+    use crate::interpreter::RuntimeError;
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
     use crate::token::{Token, TokenType::*};
-    use crate::Parser;
-    use crate::Scanner;
 
     use std::cell::RefCell;
     use std::rc::Rc;
@@ -62,6 +63,7 @@ mod tests {
                 literal: None,
                 line: 1,
             },
+            depth: None,
         };
 
         let result = expression.to_string();
@@ -79,6 +81,7 @@ mod tests {
                 ))),
                 line: 1,
             },
+            depth: None,
         };
 
         let result = expression.to_string();
@@ -88,10 +91,16 @@ mod tests {
     #[test]
     fn pretty_print_assignment() {
         let expression: Expression = Assign {
-            name: String::from("a"),
+            name: Token {
+                token_type: Identifier,
+                lexeme: String::from("a"),
+                literal: None,
+                line: 1,
+            },
             value: Box::new(Literal {
                 value: IntValue(12),
             }),
+            depth: None,
         };
 
         let result = expression.to_string();
@@ -134,7 +143,7 @@ mod tests {
         let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
             ("-1", Ok(IntValue(-1))),
             ("-1.0", Ok(FValue(-1.0))),
-            ("--12", Ok(IntValue(12))),
+            ("- -12", Ok(IntValue(12))),
             ("-true", Err(String::from("Minus not implemented for Bool"))),
         ];
 
@@ -275,6 +284,233 @@ mod tests {
         assert_eq!(results, expected_results);
     }
 
+    #[test]
+    fn test_percent_operator() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            ("7%3", Ok(IntValue(1))),
+            ("-7%3", Ok(IntValue(2))),
+            ("7%-3", Ok(IntValue(-2))),
+            ("7.5%2", Ok(FValue(1.5))),
+            ("7%2.5", Ok(FValue(2.0))),
+            (
+                "\"a\" % 2",
+                Err(String::from(
+                    "Percent operation is not implemented for: a and 2",
+                )),
+            ),
+            ("5%0", Err(String::from("Division by 0"))),
+            ("5%0.0", Err(String::from("Division by 0"))),
+            (
+                "(-9223372036854775807 - 1) % -1",
+                Err(String::from("Integer overflow in %")),
+            ),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_slash_slash_operator() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            ("7//2", Ok(IntValue(3))),
+            ("-7//2", Ok(IntValue(-4))),
+            ("7//-2", Ok(IntValue(-4))),
+            ("7.0//2", Ok(FValue(3.0))),
+            ("7//2.5", Ok(FValue(2.0))),
+            (
+                "\"a\"//2",
+                Err(String::from(
+                    "SlashSlash operation is not implemented for: a and 2",
+                )),
+            ),
+            ("5//0", Err(String::from("Division by 0"))),
+            ("5//0.0", Err(String::from("Division by 0"))),
+            (
+                "(-9223372036854775807 - 1)//-1",
+                Err(String::from("Integer overflow in //")),
+            ),
+            ("(-9223372036854775807 - 1)//1", Ok(IntValue(i64::MIN))),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_star_star_operator() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            ("2**10", Ok(IntValue(1024))),
+            ("2**3**2", Ok(IntValue(512))),
+            ("-2**2", Ok(IntValue(-4))),
+            ("2**-1", Ok(FValue(0.5))),
+            ("4**0.5", Ok(FValue(2.0))),
+            ("2.5**2", Ok(FValue(6.25))),
+            ("3*2**2", Ok(IntValue(12))),
+            ("10**20", Err(String::from("Integer overflow in **"))),
+            ("2**4294967296", Err(String::from("Integer overflow in **"))),
+            (
+                "\"a\" ** 2",
+                Err(String::from(
+                    "StarStar operation is not implemented for: a and 2",
+                )),
+            ),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_bitwise_operators() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> =
+            vec![
+            ("12 & 10", Ok(IntValue(8))),
+            ("12 | 10", Ok(IntValue(14))),
+            ("12 ^ 10", Ok(IntValue(6))),
+            ("~12", Ok(IntValue(-13))),
+            ("1 << 4", Ok(IntValue(16))),
+            ("-16 >> 2", Ok(IntValue(-4))),
+            ("6 & 3 | 8 ^ 1", Ok(IntValue(11))),
+            ("13 & 4 == 4", Ok(LiteralValue::True)),
+            ("2 < 1 << 2", Ok(LiteralValue::True)),
+            (
+                "1.5 & 1",
+                Err(String::from(
+                    "Ampersand operation is only implemented for Int operands, got Float and Int",
+                )),
+            ),
+            (
+                "\"a\" | true",
+                Err(String::from(
+                    "Pipe operation is only implemented for Int operands, got String and Bool",
+                )),
+            ),
+            (
+                "~1.0",
+                Err(String::from(
+                    "Tilde operation is only implemented for Int operands, got Float",
+                )),
+            ),
+            (
+                "1 << 64",
+                Err(String::from("Shift amount must be between 0 and 63, got 64")),
+            ),
+            (
+                "1 >> -1",
+                Err(String::from("Shift amount must be between 0 and 63, got -1")),
+            ),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_conditional_operator() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            ("true ? 1 : 2", Ok(IntValue(1))),
+            ("nil ? 1 : 2", Ok(IntValue(2))),
+            (
+                "1 > 2 ? \"a\" : 2 > 1 ? \"b\" : \"c\"",
+                Ok(LiteralValue::StringValue(String::from("b"))),
+            ),
+            ("true ? 1 : -false", Ok(IntValue(1))),
+            ("false ? -true : 2", Ok(IntValue(2))),
+            (
+                "false ? 1 : -true",
+                Err(String::from("Minus not implemented for Bool")),
+            ),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_range_expression() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            (
+                "0..3",
+                Ok(RangeValue {
+                    start: 0,
+                    end: 3,
+                    step: 1,
+                    inclusive: false,
+                }),
+            ),
+            (
+                "10..=1 + 1 step -2",
+                Ok(RangeValue {
+                    start: 10,
+                    end: 2,
+                    step: -2,
+                    inclusive: true,
+                }),
+            ),
+            ("(0..3) == (0..3)", Ok(LiteralValue::True)),
+            ("(0..3) == (0..=3)", Ok(LiteralValue::False)),
+            (
+                "1.5..3",
+                Err(String::from("Range bounds must be Int, got Float")),
+            ),
+            ("0..3 step 0", Err(String::from("Range step can't be 0"))),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
+    #[test]
+    fn test_range_slicing() {
+        let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
+            ("[1, 2, 3, 4][1..3] == [2, 3]", Ok(LiteralValue::True)),
+            (
+                "[1, 2, 3, 4][3..=0 step -2] == [4, 2]",
+                Ok(LiteralValue::True),
+            ),
+            (
+                "\"héllo\"[1..=3]",
+                Ok(LiteralValue::StringValue(String::from("éll"))),
+            ),
+            (
+                "[1, 2][0..3]",
+                Err(String::from("Index 2 out of range for list of length 2")),
+            ),
+        ];
+
+        let inputs = get_inputs(&test_cases);
+        let expected_results = get_expected_results(&test_cases);
+
+        let results = evaluate_list_of_sources(&inputs);
+
+        assert_eq!(results, expected_results);
+    }
+
     #[test]
     fn test_less_operator() {
         let test_cases: Vec<(&str, Result<LiteralValue, String>)> = vec![
@@ -377,6 +613,11 @@ mod tests {
             ("5==5", Ok(LiteralValue::True)),
             ("5!=5.5", Ok(LiteralValue::True)),
             ("\"a\" ==\"a\"", Ok(LiteralValue::True)),
+            ("5==5.0", Ok(LiteralValue::True)),
+            ("nil==nil", Ok(LiteralValue::True)),
+            ("true!=false", Ok(LiteralValue::True)),
+            ("1==\"1\"", Ok(LiteralValue::False)),
+            ("nil!=false", Ok(LiteralValue::True)),
         ];
 
         let inputs = get_inputs(&test_cases);
@@ -437,35 +678,50 @@ mod tests {
             .map(|source| {
                 let closure = move |_parent_environment: Rc<RefCell<Environment>>,
                                     _arguments: &Vec<LiteralValue>|
-                      -> Result<LiteralValue, String> {
+                      -> Result<LiteralValue, RuntimeError> {
                     Ok(LiteralValue::IntValue(2))
                 };
                 let environment = Rc::new(RefCell::new(Environment::new()));
-                environment.borrow_mut().define(
-                    String::from("clock"),
-                    LiteralValue::Callable {
-                        name: String::from("clock"),
-                        arity: 0,
-                        fun: Rc::new(closure),
-                    },
-                );
-                environment.borrow_mut().define(
-                    String::from("test"),
-                    LiteralValue::Callable {
-                        name: String::from("test"),
-                        arity: 2,
-                        fun: Rc::new(closure),
-                    },
-                );
+                environment
+                    .borrow_mut()
+                    .define(
+                        String::from("clock"),
+                        LiteralValue::Callable {
+                            name: String::from("clock"),
+                            arity: 0,
+                            max_arity: Some(0),
+                            parameters: Rc::new(vec![]),
+                            fun: Rc::new(closure),
+                            environment: environment.clone(),
+                        },
+                    )
+                    .unwrap();
+                environment
+                    .borrow_mut()
+                    .define(
+                        String::from("test"),
+                        LiteralValue::Callable {
+                            name: String::from("test"),
+                            arity: 2,
+                            max_arity: Some(2),
+                            parameters: Rc::new(vec![]),
+                            fun: Rc::new(closure),
+                            environment: environment.clone(),
+                        },
+                    )
+                    .unwrap();
 
                 environment
                     .borrow_mut()
-                    .define(String::from("testVariable"), LiteralValue::False);
+                    .define(String::from("testVariable"), LiteralValue::False)
+                    .unwrap();
                 let mut scanner: Scanner = Scanner::new(source);
                 let tokens = scanner.scan_tokens().unwrap();
                 let mut parser = Parser::new(tokens);
                 let expression = parser.expression().unwrap();
-                expression.evaluate(environment)
+                expression
+                    .evaluate(environment)
+                    .map_err(|error| error.message)
             })
             .collect::<Vec<Result<LiteralValue, String>>>()
     }
@@ -0,0 +1,275 @@
+#[cfg(test)]
+mod tests {
+    use crate::expression_literal_value::LiteralValue::{self, *};
+    use crate::interpreter::Interpreter;
+    use crate::parser::Parser;
+    use crate::resolver::Resolver;
+    use crate::scanner::Scanner;
+
+    #[test]
+    fn generator_body_runs_lazily() {
+        let interpreter = run_source(
+            "
+            var log = [];
+
+            fun numbers() {
+                push(log, \"started\");
+                yield 1;
+                push(log, \"resumed\");
+                yield 2;
+            }
+
+            var generator = numbers();
+            var before = len(log);
+            var first = generator.next();
+            var after = len(log);
+            ",
+        );
+
+        assert_eq!(get(&interpreter, "before"), IntValue(0));
+        assert_eq!(get(&interpreter, "first"), IntValue(1));
+        assert_eq!(get(&interpreter, "after"), IntValue(1));
+        assert_eq!(
+            get(&interpreter, "generator").to_string(),
+            "numbers generator"
+        );
+    }
+
+    #[test]
+    fn generator_resumes_inside_of_loops_blocks_and_match() {
+        let interpreter = run_source(
+            "
+            fun values(limit) {
+                for (var i = 0; i < limit; i++) {
+                    if (i == 1) continue;
+                    if (i == 4) break;
+                    yield i;
+                }
+
+                {
+                    var last = \"block\";
+                    yield last;
+                }
+
+                match (limit) {
+                    10 => yield \"ten\";
+                    _ => yield \"other\";
+                }
+
+                return;
+                yield \"unreachable\";
+            }
+
+            var collected = [];
+            var generator = values(10);
+            while (generator.has_next()) {
+                push(collected, generator.next());
+            }
+            ",
+        );
+
+        assert_eq!(
+            get(&interpreter, "collected").to_string(),
+            "[0, 2, 3, \"block\", \"ten\"]"
+        );
+    }
+
+    #[test]
+    fn infinite_generator_test() {
+        let interpreter = run_source(
+            "
+            fun fibonacci() {
+                var a = 0;
+                var b = 1;
+                while (true) {
+                    yield a;
+                    var next = a + b;
+                    a = b;
+                    b = next;
+                }
+            }
+
+            var generator = fibonacci();
+            var sum = 0;
+            for (var i = 0; i < 10; i++) {
+                sum += generator.next();
+            }
+            ",
+        );
+
+        assert_eq!(get(&interpreter, "sum"), IntValue(88));
+    }
+
+    #[test]
+    fn exhausted_generator_returns_error() {
+        let result = run_source_result(
+            "
+            fun once() {
+                yield;
+            }
+
+            var generator = once();
+            generator.next();
+            generator.next();
+            ",
+        );
+
+        assert_eq!(
+            result.err(),
+            Some(String::from("Generator once is exhausted"))
+        );
+    }
+
+    #[test]
+    fn failed_generator_is_finished() {
+        let interpreter = run_source(
+            "
+            fun broken() {
+                yield 1;
+                nil + 1;
+                yield 2;
+            }
+
+            var generator = broken();
+            generator.next();
+
+            var message = nil;
+            try {
+                generator.next();
+            } catch (e) {
+                message = e.message;
+            }
+
+            var more = generator.has_next();
+            ",
+        );
+
+        assert_eq!(
+            get(&interpreter, "message"),
+            StringValue(String::from(
+                "Plus operation is not implemented for: nil and 1"
+            ))
+        );
+        assert_eq!(get(&interpreter, "more"), False);
+    }
+
+    #[test]
+    fn generator_resumes_inside_of_try_catch_and_finally() {
+        let interpreter = run_source(
+            "
+            var log = [];
+
+            fun guarded() {
+                try {
+                    yield 1;
+                    throw \"failed\";
+                } catch (e) {
+                    yield e;
+                } finally {
+                    push(log, \"cleaned\");
+                    yield 3;
+                }
+
+                try {
+                    yield 4;
+                    nil + 1;
+                } finally {
+                    push(log, \"cleaned again\");
+                }
+            }
+
+            var values = [];
+            var message = nil;
+            try {
+                for (value in guarded()) {
+                    push(values, value);
+                }
+            } catch (e) {
+                message = e.message;
+            }
+            ",
+        );
+
+        assert_eq!(
+            get(&interpreter, "values").to_string(),
+            "[1, \"failed\", 3, 4]"
+        );
+        assert_eq!(
+            get(&interpreter, "log").to_string(),
+            "[\"cleaned\", \"cleaned again\"]"
+        );
+        assert_eq!(
+            get(&interpreter, "message"),
+            StringValue(String::from(
+                "Plus operation is not implemented for: nil and 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn generator_runs_finally_when_leaving_try_early() {
+        let interpreter = run_source(
+            "
+            var log = [];
+
+            fun early() {
+                while (true) {
+                    try {
+                        yield 1;
+                        break;
+                    } finally {
+                        push(log, \"break\");
+                    }
+                }
+
+                try {
+                    yield 2;
+                    return;
+                } finally {
+                    push(log, \"return\");
+                    yield 3;
+                }
+
+                yield \"unreachable\";
+            }
+
+            var values = [];
+            for (value in early()) {
+                push(values, value);
+            }
+            ",
+        );
+
+        assert_eq!(get(&interpreter, "values").to_string(), "[1, 2, 3]");
+        assert_eq!(
+            get(&interpreter, "log").to_string(),
+            "[\"break\", \"return\"]"
+        );
+    }
+
+    fn run_source_result(source: &str) -> Result<Interpreter, String> {
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens()?;
+
+        let mut parser = Parser::new(tokens);
+        let mut statements = parser.parse()?;
+
+        let mut resolver = Resolver::new();
+        resolver.resolve(&mut statements)?;
+
+        let mut interpreter = Interpreter::new();
+        interpreter
+            .interpret_statements(statements)
+            .map_err(|error| error.message)?;
+
+        Ok(interpreter)
+    }
+
+    fn run_source(source: &str) -> Interpreter {
+        run_source_result(source).unwrap()
+    }
+
+    fn get(interpreter: &Interpreter, name: &str) -> LiteralValue {
+        interpreter.environment.borrow().get(name).unwrap()
+    }
+}
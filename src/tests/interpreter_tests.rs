@@ -2,22 +2,14 @@
 mod tests {
     use crate::expression_literal_value::LiteralValue;
     use crate::Interpreter;
-    use crate::Parser;
-    use crate::Scanner;
 
     #[test]
     fn expression_test() {
         let source = "12+5;";
 
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
         assert_eq!(
@@ -30,15 +22,9 @@ mod tests {
     fn expression_initializer_with_provided_value_test() {
         let source = "var a = 12;";
 
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
         assert_eq!(
@@ -55,15 +41,9 @@ mod tests {
     fn expression_test_no_initializer_value() {
         let source = "var a;";
 
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -100,15 +80,9 @@ mod tests {
             print b;
             print c;";
 
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -147,15 +121,9 @@ mod tests {
                 c = \"hello\";
             }
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -185,15 +153,9 @@ mod tests {
                 a = a + 1;
             }
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
         assert_eq!(
@@ -214,15 +176,9 @@ mod tests {
                 print i;
             }
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -244,15 +200,9 @@ mod tests {
                 print i;
             }
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -274,15 +224,9 @@ mod tests {
                 a = a + 2;
             }
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -308,15 +252,9 @@ mod tests {
             var b = addOne(a);
             var c = clock();
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -349,15 +287,9 @@ mod tests {
 
             var b = addOne(a);
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -387,15 +319,9 @@ mod tests {
 
             var b = printA(a);
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -428,15 +354,9 @@ mod tests {
             var a = condreturn(4);
             var b = condreturn(-1);
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
-
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
-
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
@@ -473,29 +393,1417 @@ mod tests {
             var a = nested(2);
             var b = nested(1);
           ";
-        let mut scanner = Scanner::new(source);
-        let tokens = scanner.scan_tokens().unwrap();
+        let mut interpreter: Interpreter = Interpreter::new();
+        let variable_count = interpreter.environment.borrow().values.len();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().values.len(),
+            variable_count + 3
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(2))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(3))
+        );
+    }
+
+    #[test]
+    fn test_class_with_initializer_and_methods() {
+        let source = "
+            class Counter {
+                init(start) {
+                    this.count = start;
+                }
 
-        let mut parser = Parser::new(tokens);
-        let statements = parser.parse().unwrap();
+                increment() {
+                    this.count = this.count + 1;
+                    return this.count;
+                }
+            }
 
+            var counter = Counter(5);
+            counter.increment();
+            var a = counter.increment();
+            var b = counter.count;
+          ";
         let mut interpreter: Interpreter = Interpreter::new();
         let variable_count = interpreter.environment.borrow().values.len();
-        let result = interpreter.interpret_statements(statements);
+        let result = interpreter.run(source);
 
         assert!(result.is_ok());
 
         assert_eq!(
             interpreter.environment.borrow().values.len(),
-            variable_count + 3
+            variable_count + 4
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(7))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(7))
+        );
+    }
+
+    #[test]
+    fn test_class_instances_have_separate_fields() {
+        let source = "
+            class Box {}
+
+            var first = Box();
+            var second = Box();
+            first.value = 1;
+            second.value = \"two\";
+
+            var a = first.value;
+            var b = second.value;
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(1))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::StringValue(String::from("two")))
+        );
+    }
+
+    #[test]
+    fn test_class_undefined_property() {
+        let source = "
+            class Empty {}
+
+            var empty = Empty();
+            var a = empty.missing;
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Undefined property missing")));
+    }
+
+    #[test]
+    fn test_class_initializer_arity() {
+        let source = "
+            class Pair {
+                init(a, b) {
+                    this.a = a;
+                    this.b = b;
+                }
+            }
+
+            var pair = Pair(1);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Expected 2 arguments but got 1.")));
+    }
+
+    #[test]
+    fn test_class_inheritance_with_super_calls() {
+        let source = "
+            class Animal {
+                init(name) {
+                    this.name = name;
+                }
+
+                speak() {
+                    return this.name + \" makes a sound\";
+                }
+
+                kind() {
+                    return \"animal\";
+                }
+            }
+
+            class Dog < Animal {
+                speak() {
+                    return super.speak() + \" and barks\";
+                }
+            }
+
+            class Puppy < Dog {
+                init(name) {
+                    super.init(\"little \" + name);
+                }
+            }
+
+            var a = Puppy(\"Rex\").speak();
+            var b = Puppy(\"Rex\").kind();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::StringValue(String::from(
+                "little Rex makes a sound and barks"
+            )))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::StringValue(String::from("animal")))
+        );
+    }
+
+    #[test]
+    fn test_class_inherit_from_non_class() {
+        let source = "
+            var NotAClass = \"string\";
+
+            class Child < NotAClass {}
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(
+            result,
+            Err(String::from("Superclass must be a class, got String"))
+        );
+    }
+
+    #[test]
+    fn test_class_inherit_from_itself() {
+        let source = "class Oops < Oops {}";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(
+            result,
+            Err(String::from("A class Oops can't inherit from itself"))
         );
+    }
+
+    #[test]
+    fn test_closure_counter_keeps_own_state() {
+        let source = "
+            fun makeCounter() {
+                var count = 0;
+
+                fun increment() {
+                    count = count + 1;
+                    return count;
+                }
+
+                return increment;
+            }
+
+            var counter = makeCounter();
+            var other = makeCounter();
+
+            counter();
+            var a = counter();
+            var b = other();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
         assert_eq!(
             interpreter.environment.borrow().get("a"),
             Ok(LiteralValue::IntValue(2))
         );
         assert_eq!(
             interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(1))
+        );
+    }
+
+    #[test]
+    fn test_closure_passed_and_called_outside_of_declaration_scope() {
+        let source = "
+            fun makeAdder(n) {
+                fun add(x) {
+                    return x + n;
+                }
+
+                return add;
+            }
+
+            fun apply(function, value) {
+                return function(value);
+            }
+
+            var a = apply(makeAdder(5), 10);
+            var b = makeAdder(1)(1);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(15))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(2))
+        );
+    }
+
+    #[test]
+    fn test_closure_does_not_see_call_site_variables() {
+        let source = "
+            fun read() {
+                return hidden;
+            }
+
+            fun caller() {
+                var hidden = 1;
+                return read();
+            }
+
+            var a = caller();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Undefined variable hidden")));
+    }
+
+    #[test]
+    fn test_bound_method_stored_in_variable() {
+        let source = "
+            class Holder {
+                init(value) {
+                    this.value = value;
+                }
+
+                get() {
+                    return this.value;
+                }
+            }
+
+            var getter = Holder(7).get;
+            var a = getter();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(7))
+        );
+    }
+
+    #[test]
+    fn test_function_return_from_inside_while_loop() {
+        let source = "
+            fun find(target) {
+                var i = 0;
+                while (true) {
+                    if (i == target) {
+                        return i;
+                    }
+                    i = i + 1;
+                }
+            }
+
+            var a = find(3);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
             Ok(LiteralValue::IntValue(3))
         );
     }
+
+    #[test]
+    fn test_function_return_from_nested_for_loops() {
+        let source = "
+            var iterations = 0;
+
+            fun findPair(product) {
+                for (var i = 1; i < 10; i = i + 1) {
+                    for (var j = 1; j < 10; j = j + 1) {
+                        iterations = iterations + 1;
+                        {
+                            if (i * j == product) {
+                                return i * 10 + j;
+                            }
+                        }
+                    }
+                }
+
+                return -1;
+            }
+
+            var a = findPair(6);
+            var b = findPair(1000);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(16))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(-1))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("iterations"),
+            Ok(LiteralValue::IntValue(87))
+        );
+    }
+
+    #[test]
+    fn test_function_return_skips_rest_of_block() {
+        let source = "
+            var sideEffects = 0;
+
+            fun early() {
+                {
+                    if (true) {
+                        return \"early\";
+                    }
+                    sideEffects = sideEffects + 1;
+                }
+                sideEffects = sideEffects + 1;
+                return \"late\";
+            }
+
+            var a = early();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::StringValue(String::from("early")))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("sideEffects"),
+            Ok(LiteralValue::IntValue(0))
+        );
+    }
+
+    #[test]
+    fn test_recursive_function_return_inside_loop() {
+        let source = "
+            fun fib(n) {
+                while (n > 1) {
+                    return fib(n - 1) + fib(n - 2);
+                }
+                return n;
+            }
+
+            var a = fib(10);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(55))
+        );
+    }
+
+    #[test]
+    fn test_while_loop_with_break_and_continue() {
+        let source = "
+            var sum = 0;
+            var i = 0;
+            while (true) {
+                i = i + 1;
+                if (i > 10) {
+                    break;
+                }
+                if (i == 2 or i == 3) {
+                    continue;
+                }
+                sum = sum + i;
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("sum"),
+            Ok(LiteralValue::IntValue(50))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("i"),
+            Ok(LiteralValue::IntValue(11))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_continue_runs_increment() {
+        let source = "
+            var evens = 0;
+            var count = 0;
+            for (var i = 0; i < 10; i = i + 1) {
+                count = count + 1;
+                if (i == 1 or i == 3 or i == 5 or i == 7 or i == 9) {
+                    continue;
+                }
+                evens = evens + 1;
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("evens"),
+            Ok(LiteralValue::IntValue(5))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("count"),
+            Ok(LiteralValue::IntValue(10))
+        );
+    }
+
+    #[test]
+    fn test_break_only_leaves_innermost_loop() {
+        let source = "
+            var pairs = 0;
+            for (var i = 0; i < 3; i = i + 1) {
+                for (var j = 0; j < 3; j = j + 1) {
+                    if (j > i) {
+                        break;
+                    }
+                    pairs = pairs + 1;
+                }
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("pairs"),
+            Ok(LiteralValue::IntValue(6))
+        );
+    }
+
+    #[test]
+    fn test_lambda_passed_to_higher_order_function() {
+        let source = "
+            fun twice(function, value) {
+                return function(function(value));
+            }
+
+            var offset = 3;
+            var a = twice(fun (x) { return x + offset; }, 1);
+            var b = (fun () { return \"called\"; })();
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(7))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::StringValue(String::from("called")))
+        );
+    }
+
+    #[test]
+    fn test_lambda_stored_in_variable_captures_environment() {
+        let source = "
+            fun makeMultiplier(factor) {
+                return fun (x) {
+                    return x * factor;
+                };
+            }
+
+            var triple = makeMultiplier(3);
+            var a = triple(5);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(15))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("triple")
+                .map(|triple| triple.to_string()),
+            Ok(String::from("Callable: anonymous 1"))
+        );
+    }
+
+    #[test]
+    fn test_list_literal_indexing_and_assignment() {
+        let source = "
+            var xs = [1, 2, 3];
+            xs[0] = 10;
+            xs[-1] = xs[1] + 28;
+            var first = xs[0];
+            var last = xs[-1];
+            var empty = [];
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("first"),
+            Ok(LiteralValue::IntValue(10))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("last"),
+            Ok(LiteralValue::IntValue(30))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("xs")
+                .map(|xs| xs.to_string()),
+            Ok(String::from("[10, 2, 30]"))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("empty")
+                .map(|empty| empty.to_type().to_string()),
+            Ok(String::from("List"))
+        );
+    }
+
+    #[test]
+    fn test_list_is_shared_between_references() {
+        let source = "
+            fun append(list, value) {
+                push(list, value);
+            }
+
+            var xs = [1];
+            var ys = xs;
+            append(ys, 2);
+            var size = len(xs);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("size"),
+            Ok(LiteralValue::IntValue(2))
+        );
+    }
+
+    #[test]
+    fn test_list_index_out_of_range() {
+        let source = "
+            var xs = [1, 2, 3];
+            print xs[-4];
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(
+            result,
+            Err(String::from("Index -4 out of range for list of length 3"))
+        );
+    }
+
+    #[test]
+    fn test_list_index_must_be_int() {
+        let source = "
+            var xs = [1, 2, 3];
+            xs[\"a\"] = 1;
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(
+            result,
+            Err(String::from("List index must be Int, got String"))
+        );
+    }
+
+    #[test]
+    fn test_map_literal_access_and_assignment() {
+        let source = "
+            var config = {\"host\": \"localhost\", 1: true, nil: 0};
+            config[\"port\"] = 8080;
+            config[1] = false;
+            var host = config[\"host\"];
+            var port = config[\"port\"];
+            var flag = config[1];
+            var empty = {};
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("host"),
+            Ok(LiteralValue::StringValue(String::from("localhost")))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("port"),
+            Ok(LiteralValue::IntValue(8080))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("flag"),
+            Ok(LiteralValue::False)
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("config")
+                .map(|config| config.to_string()),
+            Ok(String::from(
+                "{nil: 0, 1: false, \"host\": \"localhost\", \"port\": 8080}"
+            ))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("empty")
+                .map(|empty| empty.to_type().to_string()),
+            Ok(String::from("Map"))
+        );
+    }
+
+    #[test]
+    fn test_map_missing_key() {
+        let source = "
+            var m = {\"a\": 1};
+            print m[\"b\"];
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Undefined key \"b\"")));
+    }
+
+    #[test]
+    fn test_map_key_must_be_hashable() {
+        let source = "
+            var m = {[1]: 1};
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Map key must be String, Int, Bool or Nil, got List"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_string_interpolation() {
+        let source = "
+            var name = \"Ann\";
+            var count = 2;
+            var items = [1, 2];
+            var a = \"Hello ${name}, you have ${count + 1} items\";
+            var b = \"${1}${2.5}${nil}\";
+            var c = \"first: ${items[0]}, all: ${items}\";
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::StringValue(String::from(
+                "Hello Ann, you have 3 items"
+            )))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::StringValue(String::from("12.5nil")))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("c"),
+            Ok(LiteralValue::StringValue(String::from(
+                "first: 1, all: [1, 2]"
+            )))
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_and_increments() {
+        let source = "
+            var a = 10;
+            a += 5;
+            a -= 3;
+            a *= 2;
+            a /= 4;
+            var b = a++;
+            var c = ++a;
+            var d = a--;
+            var e = --a;
+            var s = \"a\";
+            s += \"b\";
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        let expected = vec![("a", 6), ("b", 6), ("c", 8), ("d", 8), ("e", 6)];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.environment.borrow().get(name),
+                Ok(LiteralValue::IntValue(value))
+            );
+        }
+        assert_eq!(
+            interpreter.environment.borrow().get("s"),
+            Ok(LiteralValue::StringValue(String::from("ab")))
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment_evaluates_target_once() {
+        let source = "
+            class Counter {}
+            var counter = Counter();
+            counter.value = 1;
+            var items = [1, 2];
+            var calls = 0;
+
+            fun getCounter() {
+                calls++;
+                return counter;
+            }
+
+            fun getItems() {
+                calls++;
+                return items;
+            }
+
+            getCounter().value += 4;
+            var old = getCounter().value++;
+            getItems()[calls - 3] *= 10;
+            getItems()[-1]--;
+
+            var value = counter.value;
+            var first = items[0];
+            var second = items[1];
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        let expected = vec![
+            ("calls", 4),
+            ("old", 5),
+            ("value", 6),
+            ("first", 10),
+            ("second", 1),
+        ];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.environment.borrow().get(name),
+                Ok(LiteralValue::IntValue(value))
+            );
+        }
+    }
+
+    #[test]
+    fn test_conditional_expression_evaluates_only_chosen_branch() {
+        let source = "
+            var calls = 0;
+
+            fun count(value) {
+                calls += 1;
+                return value;
+            }
+
+            var a = 1 < 2 ? count(\"yes\") : count(\"no\");
+            var b = nil ? count(1) : count(2);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::StringValue(String::from("yes")))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(2))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("calls"),
+            Ok(LiteralValue::IntValue(2))
+        );
+    }
+
+    #[test]
+    fn test_functions_compare_by_identity() {
+        let source = "
+            var a = fun () { return 1; };
+            var b = fun () { return 2; };
+            var c = a;
+
+            var same = a == c;
+            var different = a == b;
+            var lists = [a] == [b];
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("same"),
+            Ok(LiteralValue::True)
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("different"),
+            Ok(LiteralValue::False)
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("lists"),
+            Ok(LiteralValue::False)
+        );
+    }
+
+    #[test]
+    fn test_functions_are_truthy() {
+        let source = "
+            fun f() {}
+            var g = fun () {};
+            var a = 0;
+
+            if (f) {
+                a = 1;
+            }
+
+            var b = g ? 1 : 2;
+            var c = !clock;
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("a"),
+            Ok(LiteralValue::IntValue(1))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("b"),
+            Ok(LiteralValue::IntValue(1))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("c"),
+            Ok(LiteralValue::False)
+        );
+    }
+
+    #[test]
+    fn test_match_statement_dispatches_on_first_matching_arm() {
+        let source = "
+            fun describe(status) {
+                match (status) {
+                    \"ok\" | \"done\" => return \"success\";
+                    404 => return \"missing\";
+                    500..600 => return \"server error\";
+                    nil => return \"unknown\";
+                    _ => return \"other\";
+                }
+            }
+
+            var a = describe(\"done\");
+            var b = describe(404.0);
+            var c = describe(503);
+            var d = describe(600);
+            var e = describe(nil);
+            var f = describe(true);
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        let expected = vec![
+            ("a", "success"),
+            ("b", "missing"),
+            ("c", "server error"),
+            ("d", "other"),
+            ("e", "unknown"),
+            ("f", "other"),
+        ];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.environment.borrow().get(name),
+                Ok(LiteralValue::StringValue(String::from(value)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_range_loops_and_patterns() {
+        let source = "
+            var total = 0;
+            for (i in 0..=10 step 5) {
+                total += i;
+            }
+
+            var parity = [];
+            for (i in 3..0 step -1) {
+                match (i) {
+                    0..=8 step 2 => push(parity, \"even\");
+                    _ => push(parity, \"odd\");
+                }
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+        assert_eq!(
+            interpreter.environment.borrow().get("total"),
+            Ok(LiteralValue::IntValue(15))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("parity")
+                .unwrap()
+                .to_string(),
+            "[\"odd\", \"even\", \"odd\"]"
+        );
+    }
+
+    #[test]
+    fn test_match_statement_without_matching_arm_and_loop_control() {
+        let source = "
+            var seen = 0;
+            match (3) {
+                1 => seen = 1;
+            }
+
+            var total = 0;
+            for (var i = 0; i < 10; i++) {
+                match (i) {
+                    1 | 2 => continue;
+                    5 => break;
+                }
+                total += i;
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("seen"),
+            Ok(LiteralValue::IntValue(0))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("total"),
+            Ok(LiteralValue::IntValue(7))
+        );
+    }
+
+    #[test]
+    fn test_try_catch_receives_thrown_value() {
+        let source = "
+            fun fail(value) {
+                throw value;
+            }
+
+            var caught = nil;
+            try {
+                fail([1, 2]);
+                caught = \"unreachable\";
+            } catch (e) {
+                caught = e;
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("caught")
+                .unwrap()
+                .to_string(),
+            "[1, 2]"
+        );
+    }
+
+    #[test]
+    fn test_try_catch_receives_thrown_value_through_iterators_and_generators() {
+        let source = "
+            class Stream {
+                has_next() {
+                    throw {\"code\": 1};
+                }
+                next() {
+                    return nil;
+                }
+            }
+
+            fun numbers() {
+                yield 1;
+                throw {\"code\": 2};
+            }
+
+            var codes = [];
+            try {
+                for (x in Stream()) {}
+            } catch (e) {
+                push(codes, e[\"code\"]);
+            }
+            try {
+                for (x in numbers()) {}
+            } catch (e) {
+                push(codes, e[\"code\"]);
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("codes")
+                .unwrap()
+                .to_string(),
+            "[1, 2]"
+        );
+    }
+
+    #[test]
+    fn test_try_catch_receives_runtime_error_with_message_and_line() {
+        let source = "
+            var message = nil;
+            var line = nil;
+            try {
+                var value = 1;
+                value = value +
+                    missing;
+            } catch (e) {
+                message = e.message;
+                line = e.line;
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("message"),
+            Ok(LiteralValue::StringValue(String::from(
+                "Undefined variable missing"
+            )))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("line"),
+            Ok(LiteralValue::IntValue(7))
+        );
+    }
+
+    #[test]
+    fn test_finally_runs_on_every_exit() {
+        let source = "
+            var log = [];
+
+            fun early() {
+                try {
+                    return 1;
+                } finally {
+                    push(log, \"return\");
+                }
+            }
+
+            var returned = early();
+
+            try {
+                try {
+                    throw \"inner\";
+                } finally {
+                    push(log, \"throw\");
+                }
+            } catch (e) {
+                push(log, e);
+            }
+
+            for (var i = 0; i < 3; i++) {
+                try {
+                    break;
+                } finally {
+                    push(log, \"break\");
+                }
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("returned"),
+            Ok(LiteralValue::IntValue(1))
+        );
+        assert_eq!(
+            interpreter
+                .environment
+                .borrow()
+                .get("log")
+                .unwrap()
+                .to_string(),
+            "[\"return\", \"throw\", \"inner\", \"break\"]"
+        );
+    }
+
+    #[test]
+    fn test_uncaught_throw_returns_error() {
+        let source = "
+            try {
+                throw \"first\";
+            } catch (e) {
+                throw e + \" again\";
+            }
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Uncaught first again at line 5")));
+    }
+
+    #[test]
+    fn test_global_constant_reassignment_returns_error() {
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run("const limit = 10;");
+
+        assert!(result.is_ok());
+
+        // the resolver of a later run doesn't know the constant, the environment still does
+        let source = "
+            fun raise() {
+                limit = limit + 1;
+            }
+
+            raise();
+          ";
+        let result = interpreter.run(source);
+
+        assert_eq!(result, Err(String::from("Cannot assign to constant limit")));
+        assert_eq!(
+            interpreter.environment.borrow().get("limit"),
+            Ok(LiteralValue::IntValue(10))
+        );
+    }
+
+    #[test]
+    fn test_global_constant_redefinition_returns_error() {
+        let mut interpreter: Interpreter = Interpreter::new();
+
+        // each source is run on its own, like separate lines of the repl
+        for (source, expected) in [
+            ("const x = 1;", Ok(())),
+            (
+                "var x = 2;",
+                Err(String::from("Cannot redefine constant x")),
+            ),
+            (
+                "fun x() {}",
+                Err(String::from("Cannot redefine constant x")),
+            ),
+        ] {
+            assert_eq!(interpreter.run(source), expected);
+        }
+
+        assert_eq!(
+            interpreter.environment.borrow().get("x"),
+            Ok(LiteralValue::IntValue(1))
+        );
+    }
+
+    #[test]
+    fn test_default_and_rest_parameters() {
+        let source = "
+            fun log(message, level = \"info\", prefix = level + \": \", ...tags) {
+                return prefix + message + \" \" + tags;
+            }
+
+            var a = log(\"started\");
+            var b = log(\"slow\", \"warn\");
+            var c = log(\"down\", \"error\", \"! \", \"db\", \"net\");
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        let expected = vec![
+            ("a", "info: started []"),
+            ("b", "warn: slow []"),
+            ("c", "! down [\"db\", \"net\"]"),
+        ];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.environment.borrow().get(name),
+                Ok(LiteralValue::StringValue(String::from(value)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_call_arity_range_errors() {
+        let cases = vec![
+            (
+                "fun f(a, b = 1) {} f(1, 2, 3);",
+                "Expected 1 to 2 arguments but got 3.",
+            ),
+            (
+                "fun f(a, ...rest) {} f();",
+                "Expected at least 1 arguments but got 0.",
+            ),
+            (
+                "class A { init(a = 1) {} } A(1, 2);",
+                "Expected 0 to 1 arguments but got 2.",
+            ),
+        ];
+
+        for (source, expected) in cases {
+            let mut interpreter: Interpreter = Interpreter::new();
+            let result = interpreter.run(source);
+
+            assert_eq!(result, Err(String::from(expected)));
+        }
+    }
+
+    #[test]
+    fn test_named_arguments() {
+        let source = "
+            fun connect(host, port = 80, secure = port == 443, ...rest) {
+                return \"${host}:${port} ${secure} ${rest}\";
+            }
+
+            class Server {
+                init(host, port = this.port()) {
+                    this.address = host + \":\" + port;
+                }
+
+                port() {
+                    return 8080;
+                }
+            }
+
+            var a = connect(host: \"a\");
+            var b = connect(port: 443, host: \"b\");
+            var c = connect(\"c\", secure: true);
+            var d = Server(host: \"d\").address;
+          ";
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.run(source);
+
+        assert!(result.is_ok());
+
+        let expected = vec![
+            ("a", "a:80 false []"),
+            ("b", "b:443 true []"),
+            ("c", "c:80 true []"),
+            ("d", "d:8080"),
+        ];
+        for (name, value) in expected {
+            assert_eq!(
+                interpreter.environment.borrow().get(name),
+                Ok(LiteralValue::StringValue(String::from(value)))
+            );
+        }
+    }
+
+    #[test]
+    fn test_named_argument_errors() {
+        let cases = vec![
+            (
+                "connect(host: \"a\", host: \"b\");",
+                "Duplicate argument host for connect",
+            ),
+            (
+                "connect(\"a\", host: \"b\");",
+                "Duplicate argument host for connect",
+            ),
+            (
+                "connect(hostname: \"a\");",
+                "Unknown argument hostname for connect",
+            ),
+            ("connect(port: 1);", "Missing argument host for connect"),
+            (
+                "connect(host: \"a\", rest: 1);",
+                "Rest parameter rest of connect can't be passed by name",
+            ),
+            ("len(list: []);", "Unknown argument list for len"),
+        ];
+
+        for (call, expected) in cases {
+            let source = format!("fun connect(host, port = 80, ...rest) {{}} {}", call);
+            let mut interpreter: Interpreter = Interpreter::new();
+            let result = interpreter.run(&source);
+
+            assert_eq!(result, Err(String::from(expected)));
+        }
+    }
 }
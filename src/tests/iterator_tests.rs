@@ -0,0 +1,85 @@
+#[cfg(test)]
+mod tests {
+    use crate::expression_literal_value::LiteralValue::{self, *};
+    use crate::iterator::ValueIterator;
+
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::rc::Rc;
+
+    #[test]
+    fn list_iterator_sees_pushed_items() {
+        let items = Rc::new(RefCell::new(vec![IntValue(1)]));
+        let mut iterator = ValueIterator::new(ListValue(items.clone()), 1).unwrap();
+
+        assert_eq!(iterator.next(), Ok(Some(IntValue(1))));
+
+        items.borrow_mut().push(IntValue(2));
+
+        assert_eq!(iterator.next(), Ok(Some(IntValue(2))));
+        assert_eq!(iterator.next(), Ok(None));
+    }
+
+    #[test]
+    fn map_iterator_returns_sorted_keys() {
+        let map = MapValue(Rc::new(RefCell::new(HashMap::new())));
+        map.set_index(&StringValue(String::from("b")), IntValue(1))
+            .unwrap();
+        map.set_index(&StringValue(String::from("a")), IntValue(2))
+            .unwrap();
+
+        assert_eq!(
+            collect(ValueIterator::new(map, 1).unwrap()),
+            vec![
+                StringValue(String::from("a")),
+                StringValue(String::from("b"))
+            ]
+        );
+    }
+
+    #[test]
+    fn string_iterator_returns_characters() {
+        let string = StringValue(String::from("añ"));
+
+        assert_eq!(
+            collect(ValueIterator::new(string, 1).unwrap()),
+            vec![
+                StringValue(String::from("a")),
+                StringValue(String::from("ñ"))
+            ]
+        );
+    }
+
+    #[test]
+    fn range_iterator_is_lazy() {
+        let range = RangeValue {
+            start: 0,
+            end: i64::MAX,
+            step: 3,
+            inclusive: false,
+        };
+        let mut iterator = ValueIterator::new(range, 1).unwrap();
+
+        assert_eq!(iterator.next(), Ok(Some(IntValue(0))));
+        assert_eq!(iterator.next(), Ok(Some(IntValue(3))));
+    }
+
+    #[test]
+    fn iterating_over_number_returns_error() {
+        let result = ValueIterator::new(IntValue(3), 7);
+
+        assert_eq!(
+            result.err().map(|error| error.message),
+            Some(String::from("Can't iterate over Int at line 7"))
+        );
+    }
+
+    fn collect(mut iterator: ValueIterator) -> Vec<LiteralValue> {
+        let mut values = vec![];
+        while let Some(value) = iterator.next().unwrap() {
+            values.push(value);
+        }
+
+        values
+    }
+}
@@ -127,6 +127,129 @@ mod tests {
         assert_eq!(lines[1], "3");
     }
 
+    #[test]
+    fn class_test() {
+        let lines = test_file("./src/tests/cases/class.lox");
+
+        assert_eq!(lines.len(), 4);
+
+        assert_eq!(lines[0], "3");
+        assert_eq!(lines[1], "9");
+        assert_eq!(lines[2], "Point instance");
+        assert_eq!(lines[3], "Point");
+    }
+
+    #[test]
+    fn closure_test() {
+        let lines = test_file("./src/tests/cases/closure.lox");
+
+        assert_eq!(lines.len(), 5);
+
+        assert_eq!(lines[0], "1");
+        assert_eq!(lines[1], "2");
+        assert_eq!(lines[2], "1");
+        assert_eq!(lines[3], "global");
+        assert_eq!(lines[4], "global");
+    }
+
+    #[test]
+    fn loop_control_test() {
+        let lines = test_file("./src/tests/cases/loop_control.lox");
+
+        assert_eq!(lines.len(), 5);
+
+        assert_eq!(lines[0], "0");
+        assert_eq!(lines[1], "1");
+        assert_eq!(lines[2], "3");
+        assert_eq!(lines[3], "4");
+        assert_eq!(lines[4], "done");
+    }
+
+    #[test]
+    fn list_test() {
+        let lines = test_file("./src/tests/cases/list.lox");
+
+        assert_eq!(lines.len(), 6);
+
+        assert_eq!(lines[0], "[0, 1, 2, 3, 4]");
+        assert_eq!(lines[1], "5");
+        assert_eq!(lines[2], "4");
+        assert_eq!(lines[3], "4");
+        assert_eq!(lines[4], "1");
+        assert_eq!(lines[5], "[\"zero\", 2, 3]");
+    }
+
+    #[test]
+    fn map_test() {
+        let lines = test_file("./src/tests/cases/map.lox");
+
+        assert_eq!(lines.len(), 7);
+
+        assert_eq!(lines[0], "27");
+        assert_eq!(lines[1], "true");
+        assert_eq!(lines[2], "true");
+        assert_eq!(lines[3], "false");
+        assert_eq!(lines[4], "[\"bob\", \"cid\"]");
+        assert_eq!(lines[5], "[27, 40]");
+        assert_eq!(lines[6], "2");
+    }
+
+    #[test]
+    fn for_in_test() {
+        let lines = test_file("./src/tests/cases/for_in.lox");
+
+        assert_eq!(
+            lines,
+            vec![
+                "1", "2", "3", "4", "a", "b", "h", "é", "!", "10", "20", "1", "3", "p", "q", "3",
+                "3", "4", "3", "5",
+            ]
+        );
+    }
+
+    #[test]
+    fn exceptions_test() {
+        let lines = test_file("./src/tests/cases/exceptions.lox");
+
+        assert_eq!(lines.len(), 5);
+
+        assert_eq!(lines[0], "a");
+        assert_eq!(lines[1], "empty record");
+        assert_eq!(lines[2], "b");
+        assert_eq!(lines[3], "Division by 0 at line 18");
+        assert_eq!(lines[4], "done");
+    }
+
+    #[test]
+    fn import_test() {
+        let lines = test_file("./src/tests/cases/modules/main.lox");
+
+        assert_eq!(lines.len(), 8);
+
+        assert_eq!(lines[0], "loading math");
+        assert_eq!(lines[1], "16");
+        assert_eq!(lines[2], "3.14");
+        assert_eq!(lines[3], "hello lox 4");
+        assert_eq!(lines[4], "true");
+        assert_eq!(lines[5], "math.lox module");
+        assert_eq!(lines[6], "3");
+        assert_eq!(lines[7], "Undefined member len of module math.lox");
+    }
+
+    #[test]
+    fn circular_import_test() {
+        let lines = test_file("./src/tests/cases/modules/circular_a.lox");
+
+        // the entry file counts as imported, so it doesn't run a second time
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0], "running a");
+        assert_eq!(
+            lines[1],
+            "Error: In module circular_b.lox: Circular import of circular_a.lox at line 1"
+        );
+    }
+
     fn test_file(file_path: &str) -> Vec<String> {
         let output = Command::new("cargo")
             .args(["run", file_path])
@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod tests {
+    use crate::environment::Environment;
+    use crate::expression_literal_value::LiteralValue::{self, *};
+    use crate::expression_literal_value::MapKey;
+    use crate::natives::define_natives;
+
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    #[test]
+    fn define_natives_test() {
+        let mut environment = Environment::new();
+        define_natives(&mut environment);
+
+        for name in [
+            "clock", "len", "push", "pop", "insert", "remove", "keys", "values", "has", "delete",
+        ] {
+            assert!(matches!(environment.get(name), Ok(Callable { .. })));
+        }
+    }
+
+    #[test]
+    fn len_test() {
+        let list = list_of(vec![IntValue(1), IntValue(2)]);
+
+        assert_eq!(call("len", vec![list]), Ok(IntValue(2)));
+        assert_eq!(
+            call("len", vec![StringValue(String::from("abc"))]),
+            Ok(IntValue(3))
+        );
+        assert_eq!(
+            call("len", vec![IntValue(1)]),
+            Err(String::from("len expects List, Map or String, got Int"))
+        );
+    }
+
+    #[test]
+    fn push_and_pop_test() {
+        let list = list_of(vec![IntValue(1)]);
+
+        assert_eq!(call("push", vec![list.clone(), IntValue(2)]), Ok(Nil));
+        assert_eq!(list, list_of(vec![IntValue(1), IntValue(2)]));
+
+        assert_eq!(call("pop", vec![list.clone()]), Ok(IntValue(2)));
+        assert_eq!(call("pop", vec![list.clone()]), Ok(IntValue(1)));
+        assert_eq!(
+            call("pop", vec![list]),
+            Err(String::from("Cannot pop from an empty list"))
+        );
+    }
+
+    #[test]
+    fn insert_and_remove_test() {
+        let list = list_of(vec![IntValue(1), IntValue(3)]);
+
+        assert_eq!(
+            call("insert", vec![list.clone(), IntValue(1), IntValue(2)]),
+            Ok(Nil)
+        );
+        assert_eq!(
+            call("insert", vec![list.clone(), IntValue(3), IntValue(4)]),
+            Ok(Nil)
+        );
+        assert_eq!(
+            list,
+            list_of(vec![IntValue(1), IntValue(2), IntValue(3), IntValue(4)])
+        );
+
+        assert_eq!(
+            call("remove", vec![list.clone(), IntValue(-1)]),
+            Ok(IntValue(4))
+        );
+        assert_eq!(
+            call("remove", vec![list.clone(), IntValue(0)]),
+            Ok(IntValue(1))
+        );
+        assert_eq!(
+            call("remove", vec![list, IntValue(5)]),
+            Err(String::from("Index 5 out of range for list of length 2"))
+        );
+    }
+
+    fn list_of(elements: Vec<LiteralValue>) -> LiteralValue {
+        ListValue(Rc::new(RefCell::new(elements)))
+    }
+
+    fn call(name: &str, arguments: Vec<LiteralValue>) -> Result<LiteralValue, String> {
+        let mut environment = Environment::new();
+        define_natives(&mut environment);
+
+        match environment.get(name)? {
+            Callable {
+                fun, environment, ..
+            } => fun(environment, &arguments).map_err(|error| error.message),
+            _ => panic!("Expected native function {}", name),
+        }
+    }
+
+    #[test]
+    fn map_natives_test() {
+        let map = map_of(vec![
+            (MapKey::String(String::from("b")), IntValue(2)),
+            (MapKey::String(String::from("a")), IntValue(1)),
+        ]);
+        let a = StringValue(String::from("a"));
+
+        assert_eq!(
+            call("keys", vec![map.clone()]),
+            Ok(list_of(vec![
+                StringValue(String::from("a")),
+                StringValue(String::from("b"))
+            ]))
+        );
+        assert_eq!(
+            call("values", vec![map.clone()]),
+            Ok(list_of(vec![IntValue(1), IntValue(2)]))
+        );
+        assert_eq!(call("has", vec![map.clone(), a.clone()]), Ok(True));
+        assert_eq!(call("delete", vec![map.clone(), a.clone()]), Ok(True));
+        assert_eq!(call("delete", vec![map.clone(), a.clone()]), Ok(False));
+        assert_eq!(call("has", vec![map.clone(), a]), Ok(False));
+        assert_eq!(call("len", vec![map]), Ok(IntValue(1)));
+    }
+
+    #[test]
+    fn map_natives_invalid_arguments_test() {
+        let map = map_of(vec![]);
+
+        assert_eq!(
+            call("keys", vec![list_of(vec![])]),
+            Err(String::from("keys expects a Map, got List"))
+        );
+        assert_eq!(
+            call("has", vec![map, list_of(vec![])]),
+            Err(String::from(
+                "Map key must be String, Int, Bool or Nil, got List"
+            ))
+        );
+    }
+
+    fn map_of(entries: Vec<(MapKey, LiteralValue)>) -> LiteralValue {
+        MapValue(Rc::new(RefCell::new(entries.into_iter().collect())))
+    }
+}
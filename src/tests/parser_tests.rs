@@ -2,11 +2,14 @@
 mod tests {
     use crate::expression::Expression::*;
     use crate::expression_literal_value::LiteralValue as ExpressionLiteralValue;
-    use crate::statement::Statement::{Block, Expression, Print, Return, Variable};
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
+    use crate::statement::Statement::{
+        Block, Class, Continue, Expression, Function, Print, Return, Variable, While,
+    };
+    use crate::statement::{Pattern, Statement};
     use crate::token::TokenType::Return as TokenReturn;
     use crate::token::{LiteralValue, Token, TokenType::*};
-    use crate::Parser;
-    use crate::Scanner;
 
     use std::string::String;
 
@@ -96,6 +99,7 @@ mod tests {
                 literal: None,
                 line: 1,
             },
+            annotation: None,
             initializer: Literal {
                 value: ExpressionLiteralValue::Nil,
             },
@@ -120,10 +124,16 @@ mod tests {
         let expression = parser.parse();
         let response = Expression {
             expression: Assign {
-                name: std::string::String::from("some_id"),
+                name: Token {
+                    token_type: Identifier,
+                    lexeme: std::string::String::from("some_id"),
+                    literal: None,
+                    line: 1,
+                },
                 value: Box::new(Literal {
                     value: ExpressionLiteralValue::IntValue(2),
                 }),
+                depth: None,
             },
         };
 
@@ -255,10 +265,16 @@ mod tests {
         let response = Block {
             statements: vec![Expression {
                 expression: Assign {
-                    name: std::string::String::from("a"),
+                    name: Token {
+                        token_type: Identifier,
+                        lexeme: std::string::String::from("a"),
+                        literal: None,
+                        line: 1,
+                    },
                     value: Box::new(Literal {
                         value: ExpressionLiteralValue::IntValue(1),
                     }),
+                    depth: None,
                 },
             }],
         };
@@ -269,4 +285,948 @@ mod tests {
         assert_eq!(string_expression.len(), 1);
         assert_eq!(string_expression[0], response);
     }
+
+    #[test]
+    fn test_property_get_and_set() {
+        let source = "a.b.c = d.e;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(set (get (defvar a) b) c (get (defvar d) e))"
+        );
+    }
+
+    #[test]
+    fn test_class_declaration() {
+        let source = "class Foo { bar() { return this; } baz(a) {} }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        let statements = statements.unwrap();
+        assert_eq!(statements.len(), 1);
+
+        match &statements[0] {
+            Class {
+                name,
+                superclass,
+                methods,
+            } => {
+                assert_eq!(name.lexeme, "Foo");
+                assert_eq!(superclass, &None);
+                assert_eq!(methods.len(), 2);
+                assert!(matches!(&methods[0], Function { name, .. } if name.lexeme == "bar"));
+                assert!(
+                    matches!(&methods[1], Function { name, parameters, .. } if name.lexeme == "baz" && parameters.len() == 1)
+                );
+            }
+            _ => panic!("Expected class declaration"),
+        }
+    }
+
+    #[test]
+    fn test_invalid_assignment_target() {
+        let source = "a.b() = 1";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert_eq!(expression, Err(String::from("Invalid assignment target =")));
+    }
+
+    #[test]
+    fn test_subclass_declaration_with_super_call() {
+        let source = "class Foo < Bar { baz() { return super.baz(); } }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        let statements = statements.unwrap();
+        assert_eq!(statements.len(), 1);
+
+        match &statements[0] {
+            Class {
+                superclass,
+                methods,
+                ..
+            } => {
+                assert_eq!(superclass.as_ref().unwrap().to_string(), "(defvar Bar)");
+
+                match &methods[0] {
+                    Function { body, .. } => match &body[0] {
+                        Return {
+                            value: Some(value), ..
+                        } => assert_eq!(value.to_string(), "((super baz) [])"),
+                        _ => panic!("Expected return statement"),
+                    },
+                    _ => panic!("Expected method"),
+                }
+            }
+            _ => panic!("Expected class declaration"),
+        }
+    }
+
+    #[test]
+    fn test_loop_control_outside_of_loop() {
+        let source = "break;\nwhile (true) { fun f() { continue; } }\nprint 1;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert_eq!(
+            statements,
+            Err(String::from(
+                "Can't use 'break' outside of a loop at line 1\n\
+                Can't use 'continue' outside of a loop at line 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_for_loop_keeps_increment_apart_from_body() {
+        let source = "for (var i = 0; i < 3; i = i + 1) { continue; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        let statements = statements.unwrap();
+        assert_eq!(statements.len(), 1);
+
+        match &statements[0] {
+            Block { statements } => match &statements[1] {
+                While {
+                    body, increment, ..
+                } => {
+                    assert!(matches!(
+                        body.as_ref(),
+                        Block { statements } if matches!(statements[0], Continue { .. })
+                    ));
+                    assert_eq!(
+                        increment.as_ref().map(|increment| increment.to_string()),
+                        Some(String::from("(= i (+ (defvar i) 1))"))
+                    );
+                }
+                _ => panic!("Expected while loop"),
+            },
+            _ => panic!("Expected block"),
+        }
+    }
+
+    #[test]
+    fn test_lambda_expression() {
+        let source = "apply(fun (a, b) { return a + b; }, 1)";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "((defvar apply) [(fun [a,b]),1])"
+        );
+    }
+
+    #[test]
+    fn test_lambda_expression_statement_is_not_declaration() {
+        let source = "fun (a) { print a; }(1);";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        let statements = statements.unwrap();
+        assert_eq!(statements.len(), 1);
+
+        match &statements[0] {
+            Expression { expression } => assert_eq!(expression.to_string(), "((fun [a]) [1])"),
+            _ => panic!("Expected expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_list_literal_and_index() {
+        let source = "[1, [2], a][0]";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(index (list [1,(list [2]),(defvar a)]) 0)"
+        );
+    }
+
+    #[test]
+    fn test_index_assignment() {
+        let source = "xs[i - 1] = 3";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(set-index (defvar xs) (- (defvar i) 1) 3)"
+        );
+    }
+
+    #[test]
+    fn test_map_literal() {
+        let source = "{\"a\": 1, b: [2]}[\"a\"]";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(index (map [a:1,(defvar b):(list [2])]) a)"
+        );
+    }
+
+    #[test]
+    fn test_map_literal_and_block_statements() {
+        let source = "{\"a\": 1}; { print 1; } {}";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        let statements = statements.unwrap();
+        assert_eq!(statements.len(), 3);
+
+        match &statements[0] {
+            Expression { expression } => assert_eq!(expression.to_string(), "(map [a:1])"),
+            _ => panic!("Expected expression statement"),
+        }
+        assert!(matches!(statements[1], Block { .. }));
+        assert!(matches!(statements[2], Block { .. }));
+    }
+
+    #[test]
+    fn test_string_interpolation() {
+        let source = "\"Hello ${name}, you have ${count + 1} items\"";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(+ (+ (+ (+ Hello  (defvar name)) , you have ) (+ (defvar count) 1))  items)"
+        );
+    }
+
+    #[test]
+    fn test_string_interpolation_without_expression_returns_error() {
+        let source = "\"a ${} b\"";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert_eq!(
+            expression,
+            Err(String::from("Expected expression at line: 1, literal: }"))
+        );
+    }
+
+    #[test]
+    fn test_bitwise_operator_precedence() {
+        let source = "a == b | c ^ d & e < f << 1 + 2";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert!(expression.is_ok());
+        assert_eq!(
+            expression.unwrap().to_string(),
+            "(== (defvar a) (| (defvar b) (^ (defvar c) (& (defvar d) (< (defvar e) (<< (defvar f) (+ 1 2)))))))"
+        );
+    }
+
+    #[test]
+    fn test_compound_assignment() {
+        let sources = vec![
+            ("a += 1", "(= a (+ (defvar a) 1))"),
+            ("a -= b * 2", "(= a (- (defvar a) (* (defvar b) 2)))"),
+            ("a.b *= 2", "(set*= (defvar a) b 2)"),
+            ("a[0] /= 2", "(set-index/= (defvar a) 0 2)"),
+            ("++a", "(= a (+ (defvar a) 1))"),
+            ("--a.b", "(set-= (defvar a) b 1)"),
+            ("a[0]++", "(post++ (index (defvar a) 0))"),
+            ("-a--", "(- (post-- (defvar a)))"),
+        ];
+
+        for (source, expected) in sources {
+            let mut scanner: Scanner = Scanner::new(source);
+
+            let tokens = scanner.scan_tokens().unwrap();
+
+            let mut parser = Parser::new(tokens);
+
+            let expression = parser.expression();
+
+            assert!(expression.is_ok());
+            assert_eq!(expression.unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_compound_assignment_invalid_target() {
+        let sources = vec![
+            ("1 += 2", "Invalid assignment target +="),
+            ("++1", "Invalid assignment target ++"),
+            ("f()--", "Invalid -- target ((defvar f) [])"),
+        ];
+
+        for (source, expected) in sources {
+            let mut scanner: Scanner = Scanner::new(source);
+
+            let tokens = scanner.scan_tokens().unwrap();
+
+            let mut parser = Parser::new(tokens);
+
+            let expression = parser.expression();
+
+            assert_eq!(expression, Err(String::from(expected)));
+        }
+    }
+
+    #[test]
+    fn test_conditional_expression() {
+        let sources = vec![
+            ("a ? b : c", "(?: (defvar a) (defvar b) (defvar c))"),
+            (
+                "a or b ? c = 1 : d ? e : f",
+                "(?: (or (defvar a) (defvar b)) (= c 1) (?: (defvar d) (defvar e) (defvar f)))",
+            ),
+            (
+                "x = a ? b : c",
+                "(= x (?: (defvar a) (defvar b) (defvar c)))",
+            ),
+        ];
+
+        for (source, expected) in sources {
+            let mut scanner: Scanner = Scanner::new(source);
+
+            let tokens = scanner.scan_tokens().unwrap();
+
+            let mut parser = Parser::new(tokens);
+
+            let expression = parser.expression();
+
+            assert!(expression.is_ok());
+            assert_eq!(expression.unwrap().to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn test_conditional_expression_without_else_returns_error() {
+        let source = "a ? b;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let expression = parser.expression();
+
+        assert_eq!(
+            expression,
+            Err(String::from(
+                "Expected ':' after then branch of conditional expression"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_match_statement() {
+        let source = "
+            match (status) {
+                \"a\" | \"b\" => print 1;
+                -1 | 2.5 => { print 2; },
+                1..5 => print 3;
+                5..=9 => print 4;
+                _ => print 5;
+            }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+        assert!(parser.warnings.is_empty());
+
+        match &statements.unwrap()[0] {
+            Statement::Match { subject, arms, .. } => {
+                assert_eq!(subject.to_string(), "(defvar status)");
+
+                let patterns = arms
+                    .iter()
+                    .map(|(patterns, _)| patterns.clone())
+                    .collect::<Vec<_>>();
+                assert_eq!(
+                    patterns,
+                    vec![
+                        vec![
+                            Pattern::Literal(ExpressionLiteralValue::StringValue(String::from(
+                                "a"
+                            ))),
+                            Pattern::Literal(ExpressionLiteralValue::StringValue(String::from(
+                                "b"
+                            ))),
+                        ],
+                        vec![
+                            Pattern::Literal(ExpressionLiteralValue::IntValue(-1)),
+                            Pattern::Literal(ExpressionLiteralValue::FValue(2.5)),
+                        ],
+                        vec![Pattern::Range {
+                            start: ExpressionLiteralValue::IntValue(1),
+                            end: ExpressionLiteralValue::IntValue(5),
+                            inclusive: false,
+                            step: None,
+                        }],
+                        vec![Pattern::Range {
+                            start: ExpressionLiteralValue::IntValue(5),
+                            end: ExpressionLiteralValue::IntValue(9),
+                            inclusive: true,
+                            step: None,
+                        }],
+                        vec![Pattern::Wildcard],
+                    ]
+                );
+                assert!(matches!(arms[1].1, Block { .. }));
+            }
+            _ => panic!("Expected match statement"),
+        }
+    }
+
+    #[test]
+    fn test_match_statement_range_pattern_with_step() {
+        let source = "match (n) { 10..=0 step -2 => print 1; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Statement::Match { arms, .. } => assert_eq!(
+                arms[0].0,
+                vec![Pattern::Range {
+                    start: ExpressionLiteralValue::IntValue(10),
+                    end: ExpressionLiteralValue::IntValue(0),
+                    inclusive: true,
+                    step: Some(-2),
+                }]
+            ),
+            _ => panic!("Expected match statement"),
+        }
+    }
+
+    #[test]
+    fn test_match_statement_range_pattern_with_float_step_returns_error() {
+        let source = "match (n) { 0..10 step 0.5 => print 1; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.unwrap_err().starts_with(
+            "Range pattern with step needs Int bounds and a non-zero Int step at line 1"
+        ));
+    }
+
+    #[test]
+    fn test_range_expression() {
+        let source = "0..n + 1 step 2 < 1..=3; step = 1;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse().unwrap();
+
+        match &statements[0] {
+            Statement::Expression { expression } => assert_eq!(
+                expression.to_string(),
+                "(< (.. 0 (+ (defvar n) 1) step 2) (..= 1 3))"
+            ),
+            _ => panic!("Expected expression statement"),
+        }
+        assert!(matches!(&statements[1], Statement::Expression { .. }));
+    }
+
+    #[test]
+    fn test_match_statement_warns_about_unreachable_arms() {
+        let source = "
+            match (status) {
+                1 | _ => print 1;
+                2 => print 2;
+                _ => print 3;
+            }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+        assert_eq!(
+            parser.warnings,
+            vec![
+                String::from("Unreachable match arm at line 4 after wildcard at line 3"),
+                String::from("Unreachable match arm at line 5 after wildcard at line 3"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_match_statement_invalid_pattern_returns_error() {
+        let source = "match (a) { b => print 1; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements
+            .unwrap_err()
+            .starts_with("Expected literal pattern at line: 1, literal: b"));
+    }
+
+    #[test]
+    fn test_try_statement() {
+        let source = "
+            try { throw 1; }
+            catch (e) { print e; }
+            finally { print 2; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Statement::Try {
+                body,
+                catch: Some((name, catch_body)),
+                finally: Some(finally),
+                ..
+            } => {
+                assert!(
+                    matches!(&body[0], Statement::Throw { value, .. } if value.to_string() == "1")
+                );
+                assert_eq!(name.lexeme, "e");
+                assert!(matches!(&catch_body[0], Print { .. }));
+                assert!(matches!(&finally[0], Print { .. }));
+            }
+            _ => panic!("Expected try statement"),
+        }
+    }
+
+    #[test]
+    fn test_try_statement_without_catch_or_finally_returns_error() {
+        let source = "try { print 1; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert_eq!(
+            statements,
+            Err(String::from(
+                "Expected 'catch' or 'finally' after try block at line 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_type_annotations() {
+        let source = "
+            var count: Int = 1;
+            const name: String = \"lox\";
+            fun f(a: String, b: Int = 2, ...rest) -> Bool { return true; }
+            var g = fun (x) -> Nil {};";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse().unwrap();
+
+        let annotation_name =
+            |annotation: &Option<Token>| annotation.as_ref().map(|token| token.lexeme.clone());
+
+        match (&statements[0], &statements[1]) {
+            (
+                Statement::Variable {
+                    annotation: variable,
+                    ..
+                },
+                Statement::Const {
+                    annotation: constant,
+                    ..
+                },
+            ) => {
+                assert_eq!(annotation_name(variable), Some(String::from("Int")));
+                assert_eq!(annotation_name(constant), Some(String::from("String")));
+            }
+            _ => panic!("Expected variable and const declarations"),
+        }
+
+        match &statements[2] {
+            Statement::Function {
+                parameters,
+                return_type,
+                ..
+            } => {
+                let annotations = parameters
+                    .iter()
+                    .map(|parameter| annotation_name(&parameter.annotation))
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    annotations,
+                    vec![
+                        Some(String::from("String")),
+                        Some(String::from("Int")),
+                        None
+                    ]
+                );
+                assert_eq!(annotation_name(return_type), Some(String::from("Bool")));
+            }
+            _ => panic!("Expected function statement"),
+        }
+
+        match &statements[3] {
+            Statement::Variable {
+                initializer: Lambda { return_type, .. },
+                ..
+            } => assert_eq!(annotation_name(return_type), Some(String::from("Nil"))),
+            _ => panic!("Expected lambda initializer"),
+        }
+    }
+
+    #[test]
+    fn test_type_annotation_without_type_name_returns_error() {
+        let source = "var count: = 1;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements
+            .unwrap_err()
+            .starts_with("Expect type name after ':'"));
+    }
+
+    #[test]
+    fn test_for_in_statement() {
+        let source = "for (x in [1, 2]) print x;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+            } => {
+                assert_eq!(variable.lexeme, "x");
+                assert_eq!(iterable.to_string(), "(list [1,2])");
+                assert!(matches!(**body, Statement::Print { .. }));
+            }
+            _ => panic!("Expected for-in statement"),
+        }
+    }
+
+    #[test]
+    fn test_for_in_statement_without_closing_paren_returns_error() {
+        let source = "for (x in xs print x;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements
+            .unwrap_err()
+            .starts_with("Expect ')' after for-in iterable"));
+    }
+
+    #[test]
+    fn test_yield_statement() {
+        let source = "fun f() { yield 1; yield; }";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Statement::Function { body, .. } => {
+                assert_eq!(body.len(), 2);
+                assert!(matches!(body[0], Statement::Yield { value: Some(_), .. }));
+                assert!(matches!(body[1], Statement::Yield { value: None, .. }));
+                assert!(body[0].contains_yield());
+            }
+            _ => panic!("Expected function statement"),
+        }
+    }
+
+    #[test]
+    fn test_import_statement() {
+        let source = "import \"lib/util.lox\" as util;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Statement::Import { path, name, .. } => {
+                assert_eq!(path.lexeme, "\"lib/util.lox\"");
+                assert_eq!(name.lexeme, "util");
+            }
+            _ => panic!("Expected import statement"),
+        }
+    }
+
+    #[test]
+    fn test_import_statement_without_as_returns_error() {
+        let source = "import \"util.lox\" util;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert_eq!(
+            statements,
+            Err(String::from("Expected 'as' after module path at line 1"))
+        );
+    }
+
+    #[test]
+    fn test_const_declaration_without_initializer_returns_error() {
+        let source = "const limit;";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements
+            .unwrap_err()
+            .starts_with("Expected '=' after constant name"));
+    }
+
+    #[test]
+    fn test_function_default_and_rest_parameters() {
+        let source = "fun log(message, level = \"info\", ...tags) {}";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Function { parameters, .. } => {
+                let parameters = parameters
+                    .iter()
+                    .map(|parameter| parameter.to_string())
+                    .collect::<Vec<_>>();
+
+                assert_eq!(parameters, vec!["message", "level=info", "...tags"]);
+            }
+            _ => panic!("Expected function"),
+        }
+    }
+
+    #[test]
+    fn test_function_invalid_parameter_order_returns_error() {
+        let cases = vec![
+            (
+                "fun f(a = 1, b) {}",
+                "Parameter b without default value follows one with default at line 1",
+            ),
+            (
+                "fun f(...a, b) {}",
+                "Rest parameter a must be the last parameter at line 1",
+            ),
+        ];
+
+        for (source, expected) in cases {
+            let mut scanner: Scanner = Scanner::new(source);
+
+            let tokens = scanner.scan_tokens().unwrap();
+
+            let mut parser = Parser::new(tokens);
+
+            assert!(parser.parse().unwrap_err().starts_with(expected));
+        }
+    }
+
+    #[test]
+    fn test_call_with_named_arguments() {
+        let source = "connect(\"a\", port: 80, secure: a ? b : c);";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements.is_ok());
+
+        match &statements.unwrap()[0] {
+            Expression { expression } => assert_eq!(
+                expression.to_string(),
+                "((defvar connect) [a,port:80,secure:(?: (defvar a) (defvar b) (defvar c))])"
+            ),
+            _ => panic!("Expected expression statement"),
+        }
+    }
+
+    #[test]
+    fn test_call_positional_after_named_argument_returns_error() {
+        let source = "connect(host: \"a\", 80);";
+        let mut scanner: Scanner = Scanner::new(source);
+
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+
+        let statements = parser.parse();
+
+        assert!(statements
+            .unwrap_err()
+            .starts_with("Positional argument after named argument host at line 1"));
+    }
 }
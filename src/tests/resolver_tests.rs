@@ -0,0 +1,249 @@
+#[cfg(test)]
+mod tests {
+    use crate::expression::Expression::*;
+    use crate::expression_literal_value::LiteralValue;
+    use crate::parser::Parser;
+    use crate::resolver::Resolver;
+    use crate::scanner::Scanner;
+    use crate::statement::Statement::{self, Block, Function, Print};
+    use crate::Interpreter;
+
+    #[test]
+    fn resolve_local_variable_depth() {
+        let statements = resolve_source("{ var a = 1; { print a; } }").unwrap();
+
+        match &statements[0] {
+            Block { statements } => match &statements[1] {
+                Block { statements } => match &statements[0] {
+                    Print {
+                        expression: Variable { depth, .. },
+                    } => assert_eq!(*depth, Some(1)),
+                    _ => panic!("Expected print of a variable"),
+                },
+                _ => panic!("Expected inner block"),
+            },
+            _ => panic!("Expected outer block"),
+        }
+    }
+
+    #[test]
+    fn resolve_global_variable_depth() {
+        let statements = resolve_source("var a = 1; fun f(b) { { print a; } }").unwrap();
+
+        match &statements[1] {
+            Function { body, .. } => match &body[0] {
+                Block { statements } => match &statements[0] {
+                    Print {
+                        expression: Variable { depth, .. },
+                    } => assert_eq!(*depth, Some(2)),
+                    _ => panic!("Expected print of a variable"),
+                },
+                _ => panic!("Expected block"),
+            },
+            _ => panic!("Expected function"),
+        }
+    }
+
+    #[test]
+    fn resolve_variable_in_own_initializer_returns_error() {
+        let result = resolve_source("{ var a = 1; { var a = a; } }");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Can't read local variable a in its own initializer at line 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_global_variable_in_own_initializer_is_allowed() {
+        let result = resolve_source("var a = 1; var a = a;");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn resolve_duplicate_declaration_returns_error() {
+        let result = resolve_source("fun f(a) { var b; var b; }");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Already a variable with name b in this scope at line 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_duplicate_parameter_returns_error() {
+        let result = resolve_source("fun f(a, a) {}");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Already a variable with name a in this scope at line 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_top_level_return_returns_error() {
+        let result = resolve_source("print 1;\nreturn 2;");
+
+        assert_eq!(
+            result,
+            Err(String::from("Can't return from top-level code at line 2"))
+        );
+    }
+
+    #[test]
+    fn resolve_this_and_super_outside_of_class_returns_errors() {
+        let result = resolve_source(
+            "print this;
+            class A { f() { return super.f(); } }",
+        );
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Can't use 'this' outside of a class at line 1\n\
+                Can't use 'super' in a class with no superclass at line 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_function_keeps_binding_of_declaration_time() {
+        let source = "
+            var a = \"global\";
+            var first;
+            var second;
+            {
+                fun getA() {
+                    return a;
+                }
+
+                first = getA();
+                var a = \"block\";
+                second = getA();
+            }
+          ";
+        let statements = resolve_source(source).unwrap();
+
+        let mut interpreter: Interpreter = Interpreter::new();
+        let result = interpreter.interpret_statements(statements);
+
+        assert!(result.is_ok());
+
+        assert_eq!(
+            interpreter.environment.borrow().get("first"),
+            Ok(LiteralValue::StringValue(String::from("global")))
+        );
+        assert_eq!(
+            interpreter.environment.borrow().get("second"),
+            Ok(LiteralValue::StringValue(String::from("global")))
+        );
+    }
+
+    #[test]
+    fn resolve_lambda_body_as_function_scope() {
+        let result = resolve_source("var f = fun (a) { var b = a; return b; };");
+
+        assert!(result.is_ok());
+
+        let result = resolve_source("var f = fun (a) { var a; };");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Already a variable with name a in this scope at line 1"
+            ))
+        );
+    }
+
+    fn resolve_source(source: &str) -> Result<Vec<Statement>, String> {
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens().unwrap();
+
+        let mut parser = Parser::new(tokens);
+        let mut statements = parser.parse().unwrap();
+
+        let mut resolver = Resolver::new();
+        resolver.resolve(&mut statements)?;
+
+        Ok(statements)
+    }
+
+    #[test]
+    fn resolve_import_outside_of_top_level_returns_error() {
+        let result = resolve_source("fun f() {\n import \"util.lox\" as util; }");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Can't import outside of top-level code at line 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_local_constant_reassignment_returns_error() {
+        let result = resolve_source("{ const a = 1;\n fun f() { a += 1; } }");
+
+        assert_eq!(
+            result,
+            Err(String::from("Can't reassign constant a declared at line 1"))
+        );
+    }
+
+    #[test]
+    fn resolve_global_constant_reassignment_returns_error() {
+        let result = resolve_source("const limit = 10;\n fun raise() { limit = 11; }");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Can't reassign constant limit declared at line 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_constant_increment_returns_error() {
+        let result = resolve_source("const a = 1;\n a++;\n { const b = 2; b--; }");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Can't reassign constant a declared at line 1\nCan't reassign constant b declared at line 3"
+            ))
+        );
+    }
+
+    #[test]
+    fn resolve_shadowed_constant_can_be_reassigned() {
+        let result = resolve_source("{ const a = 1; { var a = 2; a = 3; } }");
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn resolve_yield_outside_of_function_returns_error() {
+        let result = resolve_source("{\n yield 1; }");
+
+        assert_eq!(
+            result,
+            Err(String::from("Can't yield from top-level code at line 2"))
+        );
+    }
+
+    #[test]
+    fn resolve_yield_inside_of_try_is_allowed() {
+        let result = resolve_source(
+            "fun f() { try { yield 1; } catch (e) { yield e; } finally { yield 2; } }",
+        );
+
+        assert!(result.is_ok());
+    }
+}
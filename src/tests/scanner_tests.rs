@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
+    use crate::scanner::Scanner;
     use crate::token::{LiteralValue::*, TokenType::*};
-    use crate::Scanner;
 
     #[test]
     fn handler_one_char_tokens() {
@@ -73,6 +73,152 @@ mod tests {
         );
     }
 
+    #[test]
+    fn handler_string_literal_escape_sequences() {
+        let source = r#""line\n\ttab \"quoted\" back\\slash \u{1F600}""#;
+
+        let mut scanner = Scanner::new(source);
+
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+        assert_eq!(scanner.tokens.len(), 2);
+        assert_eq!(scanner.tokens[0].token_type, String);
+        match scanner.tokens[0].literal.as_ref().unwrap() {
+            StringValue(val) => assert_eq!(val, "line\n\ttab \"quoted\" back\\slash 😀"),
+            _ => panic!("Incorrect literal"),
+        }
+    }
+
+    #[test]
+    fn handler_string_literal_invalid_escape_returns_error() {
+        let source = "\"first\"\n\"bad \\q escape\" \"\\u{110000}\" \"\\u12\"; var a;";
+
+        let mut scanner = Scanner::new(source);
+
+        let result = scanner.scan_tokens();
+
+        assert_eq!(
+            result.err(),
+            Some(
+                "Invalid escape sequence '\\q' at line 2\n\
+                Invalid unicode escape '\\u{110000}' at line 2\n\
+                Expected '{' after '\\u' at line 2"
+                    .to_string()
+            )
+        );
+
+        // scanning resumes after the closing quote of a bad string
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![String, Semicolon, Var, Identifier, Semicolon, Eof]
+        );
+    }
+
+    #[test]
+    fn handler_non_ascii_strings_and_identifiers() {
+        let source = "var café = \"ąčęėįšųūž 日本語 😀\"; print café;";
+
+        let mut scanner = Scanner::new(source);
+
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+        assert_eq!(scanner.tokens.len(), 9);
+
+        assert_eq!(scanner.tokens[1].token_type, Identifier);
+        assert_eq!(scanner.tokens[1].lexeme, "café");
+        assert_eq!(scanner.tokens[3].token_type, String);
+        assert_eq!(scanner.tokens[3].lexeme, "\"ąčęėįšųūž 日本語 😀\"");
+        match scanner.tokens[3].literal.as_ref().unwrap() {
+            StringValue(val) => assert_eq!(val, "ąčęėįšųūž 日本語 😀"),
+            _ => panic!("Incorrect literal"),
+        }
+        assert_eq!(scanner.tokens[6].lexeme, "café");
+    }
+
+    #[test]
+    fn scan_tokens_unexpected_non_ascii_character_returns_error() {
+        let source = "var a = 1 € 2;";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert_eq!(
+            result.err(),
+            Some("Unexpected character € at line 1".to_string())
+        );
+    }
+
+    #[test]
+    fn handler_string_interpolation() {
+        let source = r#""a ${b + {"c": 1}["c"]} d ${e}" "\${f}""#;
+
+        let mut scanner = Scanner::new(source);
+
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                Interpolation,
+                Identifier,
+                Plus,
+                LeftBrace,
+                String,
+                Colon,
+                Number,
+                RightBrace,
+                LeftBracket,
+                String,
+                RightBracket,
+                RightBrace,
+                Interpolation,
+                Identifier,
+                RightBrace,
+                String,
+                String,
+                Eof
+            ]
+        );
+
+        let pieces = scanner
+            .tokens
+            .iter()
+            .filter(|token| matches!(token.token_type, Interpolation | String))
+            .map(|token| match token.literal.as_ref().unwrap() {
+                StringValue(val) => val.clone(),
+                _ => panic!("Incorrect literal"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(pieces, vec!["a ", "c", "c", " d ", "", "${f}"]);
+    }
+
+    #[test]
+    fn handler_string_interpolation_not_closed_returns_error() {
+        let source = "\"a ${b";
+
+        let mut scanner = Scanner::new(source);
+
+        let result = scanner.scan_tokens();
+
+        assert_eq!(
+            result.err(),
+            Some("Unterminated string interpolation at line 1".to_string())
+        );
+    }
+
     #[test]
     fn handler_numeral_trailing_dot_returns_int() {
         let source = "123.";
@@ -229,9 +375,63 @@ mod tests {
         assert_eq!(scanner.tokens[12].token_type, Eof);
     }
 
+    #[test]
+    fn get_loop_control_keywords() {
+        let source = "break; continue;";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens.len(), 5);
+
+        assert_eq!(scanner.tokens[0].token_type, Break);
+        assert_eq!(scanner.tokens[1].token_type, Semicolon);
+        assert_eq!(scanner.tokens[2].token_type, Continue);
+        assert_eq!(scanner.tokens[3].token_type, Semicolon);
+        assert_eq!(scanner.tokens[4].token_type, Eof);
+    }
+
+    #[test]
+    fn get_brackets() {
+        let source = "[1][0]";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens.len(), 7);
+
+        assert_eq!(scanner.tokens[0].token_type, LeftBracket);
+        assert_eq!(scanner.tokens[1].token_type, Number);
+        assert_eq!(scanner.tokens[2].token_type, RightBracket);
+        assert_eq!(scanner.tokens[3].token_type, LeftBracket);
+        assert_eq!(scanner.tokens[4].token_type, Number);
+        assert_eq!(scanner.tokens[5].token_type, RightBracket);
+        assert_eq!(scanner.tokens[6].token_type, Eof);
+    }
+
+    #[test]
+    fn get_colon() {
+        let source = "{a: 1}";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens.len(), 6);
+
+        assert_eq!(scanner.tokens[0].token_type, LeftBrace);
+        assert_eq!(scanner.tokens[1].token_type, Identifier);
+        assert_eq!(scanner.tokens[2].token_type, Colon);
+        assert_eq!(scanner.tokens[3].token_type, Number);
+        assert_eq!(scanner.tokens[4].token_type, RightBrace);
+        assert_eq!(scanner.tokens[5].token_type, Eof);
+    }
+
     #[test]
     fn scan_tokens_unexpected_character_returns_error() {
-        let source = "&";
+        let source = "@";
         let mut scanner = Scanner::new(source);
         let result = scanner.scan_tokens();
 
@@ -239,7 +439,7 @@ mod tests {
 
         assert_eq!(
             result.err(),
-            Some("Unexpected character & at line 1".to_string())
+            Some("Unexpected character @ at line 1".to_string())
         );
     }
 
@@ -251,11 +451,10 @@ mod tests {
 
         assert!(result.is_ok());
 
-        assert_eq!(scanner.tokens.len(), 3);
+        assert_eq!(scanner.tokens.len(), 2);
 
-        assert_eq!(scanner.tokens[0].token_type, Star);
-        assert_eq!(scanner.tokens[1].token_type, Star);
-        assert_eq!(scanner.tokens[2].token_type, Eof);
+        assert_eq!(scanner.tokens[0].token_type, StarStar);
+        assert_eq!(scanner.tokens[1].token_type, Eof);
     }
 
     #[test]
@@ -271,6 +470,212 @@ mod tests {
         assert_eq!(scanner.tokens[0].token_type, Eof);
     }
 
+    #[test]
+    fn scan_arithmetic_operators() {
+        let source = "a % b ** c// d; // comment\n(e)// [f][0]//2; // comment";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                Identifier,
+                Percent,
+                Identifier,
+                StarStar,
+                Identifier,
+                SlashSlash,
+                Identifier,
+                Semicolon,
+                LeftParen,
+                Identifier,
+                RightParen,
+                SlashSlash,
+                LeftBracket,
+                Identifier,
+                RightBracket,
+                LeftBracket,
+                Number,
+                RightBracket,
+                SlashSlash,
+                Number,
+                Semicolon,
+                Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_comment_after_operand() {
+        let source = "if (x) // only when x\n print 1;\nvar b = a // comment\n + 1;";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                If, LeftParen, Identifier, RightParen, Print, Number, Semicolon, Var, Identifier,
+                Equal, Identifier, Plus, Number, Semicolon, Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_bitwise_operators() {
+        let source = "a & b | c ^ ~d << 1 >> 2 <= 3 >= 4";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                Identifier,
+                Ampersand,
+                Identifier,
+                Pipe,
+                Identifier,
+                Caret,
+                Tilde,
+                Identifier,
+                LessLess,
+                Number,
+                GreaterGreater,
+                Number,
+                LessEqual,
+                Number,
+                GreaterEqual,
+                Number,
+                Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_compound_assignment_operators() {
+        let source = "a += 1 -= 2 *= 3 /= 4; a++ - --b";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                Identifier, PlusEqual, Number, MinusEqual, Number, StarEqual, Number, SlashEqual,
+                Number, Semicolon, Identifier, PlusPlus, Minus, MinusMinus, Identifier, Eof
+            ]
+        );
+    }
+
+    #[test]
+    fn scan_conditional_operator() {
+        let source = "a ? b : c";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens.len(), 6);
+
+        assert_eq!(scanner.tokens[1].token_type, Question);
+        assert_eq!(scanner.tokens[3].token_type, Colon);
+        assert_eq!(scanner.tokens[5].token_type, Eof);
+    }
+
+    #[test]
+    fn scan_rest_parameter_token() {
+        let source = "fun f(...rest)";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens[3].token_type, DotDotDot);
+        assert_eq!(scanner.tokens[4].lexeme, "rest");
+    }
+
+    #[test]
+    fn scan_arrow_token() {
+        let source = "-> - -= --";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(token_types, vec![Arrow, Minus, MinusEqual, MinusMinus, Eof]);
+    }
+
+    #[test]
+    fn scan_in_keyword() {
+        let source = "for (x in xs)";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        assert_eq!(scanner.tokens[3].token_type, In);
+        assert_eq!(scanner.tokens[4].token_type, Identifier);
+    }
+
+    #[test]
+    fn scan_match_tokens() {
+        let source = "match 1..2 ..= => _";
+        let mut scanner = Scanner::new(source);
+        let result = scanner.scan_tokens();
+
+        assert!(result.is_ok());
+
+        let token_types = scanner
+            .tokens
+            .iter()
+            .map(|token| token.token_type.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_types,
+            vec![
+                Match,
+                Number,
+                DotDot,
+                Number,
+                DotDotEqual,
+                FatArrow,
+                Identifier,
+                Eof
+            ]
+        );
+    }
+
     #[test]
     fn scan_multi_line_string_returns_string() {
         let source = "\"hello\nworld\"";
@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::parser::Parser;
+    use crate::scanner::Scanner;
+    use crate::typechecker::TypeChecker;
+
+    #[test]
+    fn annotated_code_without_mismatches_passes() {
+        let result = check_source(
+            "
+            var count: Int = 1;
+            const name: String = \"lox\";
+            var ratio: Float = 0.5 * 2;
+            var same: Bool = count == 1;
+
+            fun repeat(text: String, times: Int = 2) -> String {
+                return text;
+            }
+
+            var result: String = repeat(name, times: count + 1);
+
+            for (i in 0..3) {
+                var index: Int = i;
+            }
+            ",
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn unannotated_code_stays_dynamic() {
+        let result = check_source(
+            "
+            var value = 1;
+            value = \"one\";
+
+            fun identity(x) {
+                return x;
+            }
+
+            var text: String = identity(1);
+            var number: Int = value;
+            ",
+        );
+
+        assert_eq!(result, Ok(()));
+    }
+
+    #[test]
+    fn variable_mismatches_return_errors() {
+        let result = check_source(
+            "var count: Int = \"one\";\nconst flag: Bool = nil;\ncount = 2.5;\nvar name: String;",
+        );
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Cannot initialize count of type Int with String at line 1\n\
+                 Cannot initialize flag of type Bool with Nil at line 2\n\
+                 Cannot assign Float to count declared as Int at line 3\n\
+                 Cannot initialize name of type String with Nil at line 4"
+            ))
+        );
+    }
+
+    #[test]
+    fn function_mismatches_return_errors() {
+        let result = check_source(
+            "
+            var area = fun (width: Int, height: Int = 1.0) -> Int {
+                if (width < 0) return;
+                return width * height;
+            };
+            var label: String = twice(\"a\", other: 1);
+            area(\"wide\", height: true);
+
+            fun twice(value: Int, other: Int) -> Int {
+                return value + value;
+            }",
+        );
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Default value of height in lambda should be Int but is Float at line 2\n\
+                 Cannot return Nil from lambda declared to return Int at line 3\n\
+                 Argument value of twice should be Int but is String at line 6\n\
+                 Cannot initialize label of type String with Int at line 6\n\
+                 Argument width of area should be Int but is String at line 7\n\
+                 Argument height of area should be Int but is Bool at line 7"
+            ))
+        );
+    }
+
+    #[test]
+    fn unknown_type_returns_error() {
+        let result = check_source("var count: Integer = 1;\nfun f() -> Text {}");
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Unknown type Integer at line 1\nUnknown type Text at line 2"
+            ))
+        );
+    }
+
+    #[test]
+    fn shadowed_variable_uses_innermost_annotation() {
+        let result = check_source(
+            "
+            var value: Int = 1;
+            {
+                var value: String = \"inner\";
+                value = \"still a string\";
+            }
+            value = \"not an int\";",
+        );
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Cannot assign String to value declared as Int at line 7"
+            ))
+        );
+    }
+
+    #[test]
+    fn generator_functions_return_generators() {
+        let result = check_source(
+            "
+            fun numbers() -> Generator {
+                yield 1;
+                return;
+            }
+            var stream: Generator = numbers();
+
+            fun letters() -> String {
+                yield \"a\";
+            }",
+        );
+
+        assert_eq!(
+            result,
+            Err(String::from(
+                "Cannot return Generator from letters declared to return String at line 8"
+            ))
+        );
+    }
+
+    fn check_source(source: &str) -> Result<(), String> {
+        let mut scanner = Scanner::new(source);
+        let tokens = scanner.scan_tokens()?;
+
+        let mut parser = Parser::new(tokens);
+        let statements = parser.parse()?;
+
+        let mut type_checker = TypeChecker::new();
+        type_checker.check(&statements)
+    }
+}
@@ -8,13 +8,22 @@ pub enum TokenType {
     RightParen,
     LeftBrace,
     RightBrace,
+    LeftBracket,
+    RightBracket,
     Comma,
+    Colon,
+    Question,
     Dot,
     Minus,
     Plus,
     Semicolon,
     Slash,
     Star,
+    Percent,
+    Ampersand,
+    Pipe,
+    Caret,
+    Tilde,
 
     // One or two character tokens.
     Bang,
@@ -25,29 +34,56 @@ pub enum TokenType {
     GreaterEqual,
     Less,
     LessEqual,
+    SlashSlash,
+    StarStar,
+    FatArrow,
+    DotDot,
+    DotDotEqual,
+    DotDotDot,
+    LessLess,
+    GreaterGreater,
+    PlusEqual,
+    MinusEqual,
+    StarEqual,
+    SlashEqual,
+    PlusPlus,
+    MinusMinus,
+    Arrow,
 
     // Literals.
     Identifier,
     String,
+    Interpolation,
     Number,
 
     // Keywords.
     And,
+    Break,
+    Catch,
     Class,
+    Const,
+    Continue,
     Else,
     False,
+    Finally,
     Fun,
     For,
     If,
+    Import,
+    In,
+    Match,
     Nil,
     Or,
     Print,
     Return,
     Super,
     This,
+    Throw,
     True,
+    Try,
     Var,
     While,
+    Yield,
 
     Eof,
 }
@@ -61,13 +97,22 @@ impl FromStr for TokenType {
             "RightParen" => TokenType::RightParen,
             "LeftBrace" => TokenType::LeftBrace,
             "RightBrace" => TokenType::RightBrace,
+            "LeftBracket" => TokenType::LeftBracket,
+            "RightBracket" => TokenType::RightBracket,
             "Comma" => TokenType::Comma,
+            ":" | "Colon" => TokenType::Colon,
+            "?" | "Question" => TokenType::Question,
             "Dot" => TokenType::Dot,
             "-" | "Minus" => TokenType::Minus,
             "+" | "Plus" => TokenType::Plus,
             ";" => TokenType::Semicolon,
             "/" | "Slash" => TokenType::Slash,
             "*" | "Star" => TokenType::Star,
+            "%" | "Percent" => TokenType::Percent,
+            "&" | "Ampersand" => TokenType::Ampersand,
+            "|" | "Pipe" => TokenType::Pipe,
+            "^" | "Caret" => TokenType::Caret,
+            "~" | "Tilde" => TokenType::Tilde,
 
             // One or two character tokens
             "!" => TokenType::Bang,
@@ -78,28 +123,55 @@ impl FromStr for TokenType {
             ">=" | "GreaterEqual" => TokenType::GreaterEqual,
             "<" | "Less" => TokenType::Less,
             "<=" | "LessEqual" => TokenType::LessEqual,
+            "//" | "SlashSlash" => TokenType::SlashSlash,
+            "**" | "StarStar" => TokenType::StarStar,
+            "=>" | "FatArrow" => TokenType::FatArrow,
+            ".." | "DotDot" => TokenType::DotDot,
+            "..=" | "DotDotEqual" => TokenType::DotDotEqual,
+            "..." | "DotDotDot" => TokenType::DotDotDot,
+            "<<" | "LessLess" => TokenType::LessLess,
+            ">>" | "GreaterGreater" => TokenType::GreaterGreater,
+            "+=" | "PlusEqual" => TokenType::PlusEqual,
+            "-=" | "MinusEqual" => TokenType::MinusEqual,
+            "*=" | "StarEqual" => TokenType::StarEqual,
+            "/=" | "SlashEqual" => TokenType::SlashEqual,
+            "++" | "PlusPlus" => TokenType::PlusPlus,
+            "--" | "MinusMinus" => TokenType::MinusMinus,
+            "->" | "Arrow" => TokenType::Arrow,
 
             // Keywords
             "And" => TokenType::And,
+            "Break" => TokenType::Break,
+            "Catch" => TokenType::Catch,
             "Class" => TokenType::Class,
+            "Const" => TokenType::Const,
+            "Continue" => TokenType::Continue,
             "Else" => TokenType::Else,
             "False" => TokenType::False,
+            "Finally" => TokenType::Finally,
             "Fun" => TokenType::Fun,
             "For" => TokenType::For,
             "If" => TokenType::If,
+            "Import" => TokenType::Import,
+            "In" => TokenType::In,
+            "Match" => TokenType::Match,
             "Nil" => TokenType::Nil,
             "Or" => TokenType::Or,
             "Print" => TokenType::Print,
             "Return" => TokenType::Return,
             "Super" => TokenType::Super,
             "This" => TokenType::This,
+            "Throw" => TokenType::Throw,
             "True" => TokenType::True,
+            "Try" => TokenType::Try,
             "Var" => TokenType::Var,
             "While" => TokenType::While,
+            "Yield" => TokenType::Yield,
 
             // Literals
             "Identifier" => TokenType::Identifier,
             "String" => TokenType::String,
+            "Interpolation" => TokenType::Interpolation,
             "Number" => TokenType::Number,
 
             // End of file
@@ -0,0 +1,702 @@
+use crate::expression::Expression;
+use crate::statement::{Parameter, Statement};
+use crate::token::{Token, TokenType};
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+#[cfg(test)]
+#[path = "./tests/typechecker_tests.rs"]
+mod tests;
+
+// the names LiteralValue::to_type gives values at runtime
+const TYPE_NAMES: [&str; 14] = [
+    "Int",
+    "Float",
+    "String",
+    "Bool",
+    "Nil",
+    "List",
+    "Map",
+    "Callable",
+    "Class",
+    "Instance",
+    "Error",
+    "Module",
+    "Generator",
+    "Range",
+];
+
+struct Signature {
+    name: String,
+    parameters: Vec<Parameter>,
+    returns: Option<String>,
+}
+
+// what is known about a name before running, a missing type keeps it dynamic
+#[derive(Clone)]
+struct Binding {
+    type_name: Option<String>,
+    signature: Option<Rc<Signature>>,
+}
+
+pub struct TypeChecker {
+    scopes: Vec<HashMap<String, Binding>>,
+    // name and declared return type of the functions being checked, innermost last
+    functions: Vec<(String, Option<String>)>,
+    errors: Vec<String>,
+}
+
+impl TypeChecker {
+    pub fn new() -> Self {
+        TypeChecker {
+            scopes: vec![HashMap::new()],
+            functions: vec![],
+            errors: vec![],
+        }
+    }
+
+    pub fn check(&mut self, statements: &[Statement]) -> Result<(), String> {
+        self.check_statements(statements);
+
+        if !self.errors.is_empty() {
+            return Err(self.errors.join("\n"));
+        }
+
+        Ok(())
+    }
+
+    fn check_statements(&mut self, statements: &[Statement]) {
+        // functions can be called before their declaration, so their signatures come first
+        for statement in statements {
+            if let Statement::Function {
+                name,
+                parameters,
+                return_type,
+                ..
+            } = statement
+            {
+                self.declare_function(name, parameters, return_type);
+            }
+        }
+
+        for statement in statements {
+            self.check_statement(statement);
+        }
+    }
+
+    fn check_statement(&mut self, statement: &Statement) {
+        match statement {
+            Statement::Expression { expression } | Statement::Print { expression } => {
+                self.type_of(expression);
+            }
+            Statement::Variable {
+                token,
+                annotation,
+                initializer,
+            }
+            | Statement::Const {
+                token,
+                annotation,
+                initializer,
+            } => {
+                let actual = self.type_of(initializer);
+                let annotation = self.annotation_name(annotation);
+
+                if let Some(expected) = &annotation {
+                    if Self::mismatch(expected, &actual) {
+                        self.errors.push(format!(
+                            "Cannot initialize {} of type {} with {} at line {}",
+                            token.lexeme,
+                            expected,
+                            actual.unwrap_or_default(),
+                            token.line
+                        ));
+                    }
+                }
+
+                let signature = match initializer {
+                    Expression::Lambda {
+                        parameters,
+                        return_type,
+                        ..
+                    } => Some(self.signature(&token.lexeme, parameters, return_type)),
+                    _ => None,
+                };
+
+                self.declare(
+                    &token.lexeme,
+                    Binding {
+                        type_name: annotation,
+                        signature,
+                    },
+                );
+            }
+            Statement::Block { statements } => {
+                self.scopes.push(HashMap::new());
+                self.check_statements(statements);
+                self.scopes.pop();
+            }
+            Statement::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.type_of(condition);
+                self.check_statement(then_branch);
+
+                if let Some(else_branch) = else_branch {
+                    self.check_statement(else_branch);
+                }
+            }
+            Statement::While {
+                condition,
+                body,
+                increment,
+            } => {
+                self.type_of(condition);
+                self.check_statement(body);
+
+                if let Some(increment) = increment {
+                    self.type_of(increment);
+                }
+            }
+            Statement::ForIn {
+                variable,
+                iterable,
+                body,
+            } => {
+                let type_name = match self.type_of(iterable).as_deref() {
+                    Some("Range") => Some(String::from("Int")),
+                    Some("String") => Some(String::from("String")),
+                    _ => None,
+                };
+
+                self.scopes.push(HashMap::new());
+                self.declare(
+                    &variable.lexeme,
+                    Binding {
+                        type_name,
+                        signature: None,
+                    },
+                );
+                self.check_statement(body);
+                self.scopes.pop();
+            }
+            Statement::Function {
+                name,
+                parameters,
+                return_type,
+                body,
+            } => {
+                self.declare_function(name, parameters, return_type);
+                self.check_function(&name.lexeme, parameters, return_type, body);
+            }
+            Statement::Class {
+                name,
+                superclass,
+                methods,
+            } => {
+                self.declare(
+                    &name.lexeme,
+                    Binding {
+                        type_name: Some(String::from("Class")),
+                        signature: None,
+                    },
+                );
+
+                if let Some(superclass) = superclass {
+                    self.type_of(superclass);
+                }
+
+                for method in methods {
+                    if let Statement::Function {
+                        name,
+                        parameters,
+                        return_type,
+                        body,
+                    } = method
+                    {
+                        self.check_function(&name.lexeme, parameters, return_type, body);
+                    }
+                }
+            }
+            Statement::Return { keyword, value } => {
+                let actual = match value {
+                    Some(value) => self.type_of(value),
+                    None => Some(String::from("Nil")),
+                };
+
+                if let Some((name, Some(expected))) = self.functions.last() {
+                    if Self::mismatch(expected, &actual) {
+                        self.errors.push(format!(
+                            "Cannot return {} from {} declared to return {} at line {}",
+                            actual.unwrap_or_default(),
+                            name,
+                            expected,
+                            keyword.line
+                        ));
+                    }
+                }
+            }
+            Statement::Match { subject, arms, .. } => {
+                self.type_of(subject);
+
+                for (_, body) in arms {
+                    self.check_statement(body);
+                }
+            }
+            Statement::Import { name, .. } => self.declare(
+                &name.lexeme,
+                Binding {
+                    type_name: Some(String::from("Module")),
+                    signature: None,
+                },
+            ),
+            Statement::Yield { value, .. } => {
+                if let Some(value) = value {
+                    self.type_of(value);
+                }
+            }
+            Statement::Throw { value, .. } => {
+                self.type_of(value);
+            }
+            Statement::Try {
+                body,
+                catch,
+                finally,
+                ..
+            } => {
+                self.scopes.push(HashMap::new());
+                self.check_statements(body);
+                self.scopes.pop();
+
+                // anything can be thrown, so the caught value stays dynamic
+                if let Some((name, statements)) = catch {
+                    self.scopes.push(HashMap::new());
+                    self.declare(
+                        &name.lexeme,
+                        Binding {
+                            type_name: None,
+                            signature: None,
+                        },
+                    );
+                    self.check_statements(statements);
+                    self.scopes.pop();
+                }
+
+                if let Some(statements) = finally {
+                    self.scopes.push(HashMap::new());
+                    self.check_statements(statements);
+                    self.scopes.pop();
+                }
+            }
+            Statement::Break { .. } | Statement::Continue { .. } => {}
+        }
+    }
+
+    fn check_function(
+        &mut self,
+        name: &str,
+        parameters: &[Parameter],
+        return_type: &Option<Token>,
+        body: &[Statement],
+    ) {
+        let returns = self.annotation_name(return_type);
+
+        self.scopes.push(HashMap::new());
+
+        for parameter in parameters {
+            let annotation = self.annotation_name(&parameter.annotation);
+
+            if let (Some(expected), Some(default)) = (&annotation, &parameter.default) {
+                let actual = self.type_of(default);
+
+                if Self::mismatch(expected, &actual) {
+                    self.errors.push(format!(
+                        "Default value of {} in {} should be {} but is {} at line {}",
+                        parameter.name.lexeme,
+                        name,
+                        expected,
+                        actual.unwrap_or_default(),
+                        parameter.name.line
+                    ));
+                }
+            }
+
+            let type_name = match parameter.rest {
+                true => Some(String::from("List")),
+                false => annotation,
+            };
+
+            self.declare(
+                &parameter.name.lexeme,
+                Binding {
+                    type_name,
+                    signature: None,
+                },
+            );
+        }
+
+        // calling a generator function gives a Generator, its returns only end the generator
+        let returns = match (body.iter().any(Statement::contains_yield), returns) {
+            (true, Some(expected)) => {
+                if Self::mismatch(&expected, &Some(String::from("Generator"))) {
+                    self.errors.push(format!(
+                        "Cannot return Generator from {} declared to return {} at line {}",
+                        name,
+                        expected,
+                        return_type.as_ref().map_or(0, |token| token.line)
+                    ));
+                }
+
+                None
+            }
+            (true, None) => None,
+            (false, returns) => returns,
+        };
+
+        self.functions.push((String::from(name), returns));
+        self.check_statements(body);
+        self.functions.pop();
+
+        self.scopes.pop();
+    }
+
+    fn type_of(&mut self, expression: &Expression) -> Option<String> {
+        match expression {
+            Expression::Literal { value } => Some(String::from(value.to_type())),
+            Expression::Grouping { group } => self.type_of(group),
+            Expression::Variable { token, .. } => self.lookup(&token.lexeme)?.type_name,
+            Expression::Assign { name, value, .. } => {
+                let actual = self.type_of(value);
+
+                if let Some(Binding {
+                    type_name: Some(expected),
+                    ..
+                }) = self.lookup(&name.lexeme)
+                {
+                    if Self::mismatch(&expected, &actual) {
+                        self.errors.push(format!(
+                            "Cannot assign {} to {} declared as {} at line {}",
+                            actual.clone().unwrap_or_default(),
+                            name.lexeme,
+                            expected,
+                            name.line
+                        ));
+                    }
+                }
+
+                actual
+            }
+            Expression::Unary { operator, right } => {
+                let right = self.type_of(right);
+
+                match (&operator.token_type, right.as_deref()) {
+                    (TokenType::Bang, _) => Some(String::from("Bool")),
+                    (TokenType::Minus, Some("Int" | "Float")) => right,
+                    (TokenType::Tilde, Some("Int")) => right,
+                    _ => None,
+                }
+            }
+            Expression::Binary {
+                left,
+                operator,
+                right,
+            } => {
+                let left = self.type_of(left);
+                let right = self.type_of(right);
+
+                Self::binary_type(&operator.token_type, left.as_deref(), right.as_deref())
+            }
+            Expression::Logical { left, right, .. } => {
+                let left = self.type_of(left);
+                let right = self.type_of(right);
+
+                if left == right {
+                    left
+                } else {
+                    None
+                }
+            }
+            Expression::Conditional {
+                condition,
+                then_branch,
+                else_branch,
+            } => {
+                self.type_of(condition);
+                let then_type = self.type_of(then_branch);
+                let else_type = self.type_of(else_branch);
+
+                if then_type == else_type {
+                    then_type
+                } else {
+                    None
+                }
+            }
+            Expression::Range {
+                start, end, step, ..
+            } => {
+                self.type_of(start);
+                self.type_of(end);
+
+                if let Some(step) = step {
+                    self.type_of(step);
+                }
+
+                Some(String::from("Range"))
+            }
+            Expression::Call {
+                callee,
+                paren,
+                arguments,
+                named,
+            } => {
+                let callee_type = self.type_of(callee);
+
+                let actual = arguments
+                    .iter()
+                    .map(|argument| self.type_of(argument))
+                    .collect::<Vec<Option<String>>>();
+                let named = named
+                    .iter()
+                    .map(|(name, argument)| (name.lexeme.clone(), self.type_of(argument)))
+                    .collect::<Vec<(String, Option<String>)>>();
+
+                let signature = match callee.as_ref() {
+                    Expression::Variable { token, .. } => self
+                        .lookup(&token.lexeme)
+                        .and_then(|binding| binding.signature),
+                    _ => None,
+                };
+
+                match signature {
+                    Some(signature) => {
+                        self.check_arguments(&signature, &actual, &named, paren.line);
+                        signature.returns.clone()
+                    }
+                    None if callee_type.as_deref() == Some("Class") => {
+                        Some(String::from("Instance"))
+                    }
+                    None => None,
+                }
+            }
+            Expression::Get { object, .. } => {
+                self.type_of(object);
+                None
+            }
+            Expression::Set { object, value, .. } => {
+                self.type_of(object);
+                self.type_of(value);
+                None
+            }
+            Expression::List { elements } => {
+                for element in elements {
+                    self.type_of(element);
+                }
+
+                Some(String::from("List"))
+            }
+            Expression::Map { entries } => {
+                for (key, value) in entries {
+                    self.type_of(key);
+                    self.type_of(value);
+                }
+
+                Some(String::from("Map"))
+            }
+            Expression::Index { object, index, .. } => {
+                let object = self.type_of(object);
+                let index = self.type_of(index);
+
+                match (object.as_deref(), index.as_deref()) {
+                    (Some("List" | "String"), Some("Range")) => object,
+                    _ => None,
+                }
+            }
+            Expression::SetIndex {
+                object,
+                index,
+                value,
+                ..
+            } => {
+                self.type_of(object);
+                self.type_of(index);
+                self.type_of(value);
+                None
+            }
+            Expression::Postfix { target, .. } => match self.type_of(target).as_deref() {
+                Some("Int") => Some(String::from("Int")),
+                Some("Float") => Some(String::from("Float")),
+                _ => None,
+            },
+            Expression::This { .. } => Some(String::from("Instance")),
+            Expression::Super { .. } => None,
+            Expression::Lambda {
+                parameters,
+                return_type,
+                body,
+                ..
+            } => {
+                self.check_function("lambda", parameters, return_type, body);
+                Some(String::from("Callable"))
+            }
+        }
+    }
+
+    // only results that are certain are given a type, everything else stays dynamic
+    fn binary_type(
+        operator: &TokenType,
+        left: Option<&str>,
+        right: Option<&str>,
+    ) -> Option<String> {
+        let type_name = match (operator, left, right) {
+            (
+                TokenType::EqualEqual
+                | TokenType::BangEqual
+                | TokenType::Greater
+                | TokenType::GreaterEqual
+                | TokenType::Less
+                | TokenType::LessEqual,
+                _,
+                _,
+            ) => "Bool",
+            (TokenType::Plus, Some("String"), Some("String")) => "String",
+            (TokenType::Plus | TokenType::Minus | TokenType::Star, Some("Int"), Some("Int")) => {
+                "Int"
+            }
+            (
+                TokenType::Plus | TokenType::Minus | TokenType::Star,
+                Some("Int" | "Float"),
+                Some("Int" | "Float"),
+            ) => "Float",
+            (
+                TokenType::Ampersand
+                | TokenType::Pipe
+                | TokenType::Caret
+                | TokenType::LessLess
+                | TokenType::GreaterGreater,
+                Some("Int"),
+                Some("Int"),
+            ) => "Int",
+            _ => return None,
+        };
+
+        Some(String::from(type_name))
+    }
+
+    fn check_arguments(
+        &mut self,
+        signature: &Signature,
+        positional: &[Option<String>],
+        named: &[(String, Option<String>)],
+        line: usize,
+    ) {
+        let mut arguments = positional
+            .iter()
+            .zip(
+                signature
+                    .parameters
+                    .iter()
+                    .filter(|parameter| !parameter.rest),
+            )
+            .map(|(actual, parameter)| (parameter, actual.clone()))
+            .collect::<Vec<(&Parameter, Option<String>)>>();
+
+        for (name, actual) in named {
+            if let Some(parameter) = signature
+                .parameters
+                .iter()
+                .find(|parameter| &parameter.name.lexeme == name)
+            {
+                arguments.push((parameter, actual.clone()));
+            }
+        }
+
+        for (parameter, actual) in arguments {
+            let expected = match &parameter.annotation {
+                Some(annotation) if TYPE_NAMES.contains(&annotation.lexeme.as_str()) => {
+                    &annotation.lexeme
+                }
+                _ => continue,
+            };
+
+            if Self::mismatch(expected, &actual) {
+                self.errors.push(format!(
+                    "Argument {} of {} should be {} but is {} at line {}",
+                    parameter.name.lexeme,
+                    signature.name,
+                    expected,
+                    actual.unwrap_or_default(),
+                    line
+                ));
+            }
+        }
+    }
+
+    fn declare_function(
+        &mut self,
+        name: &Token,
+        parameters: &[Parameter],
+        return_type: &Option<Token>,
+    ) {
+        let signature = self.signature(&name.lexeme, parameters, return_type);
+
+        self.declare(
+            &name.lexeme,
+            Binding {
+                type_name: Some(String::from("Callable")),
+                signature: Some(signature),
+            },
+        );
+    }
+
+    fn signature(
+        &self,
+        name: &str,
+        parameters: &[Parameter],
+        return_type: &Option<Token>,
+    ) -> Rc<Signature> {
+        Rc::new(Signature {
+            name: String::from(name),
+            parameters: parameters.to_vec(),
+            returns: return_type
+                .as_ref()
+                .filter(|token| TYPE_NAMES.contains(&token.lexeme.as_str()))
+                .map(|token| token.lexeme.clone()),
+        })
+    }
+
+    // unknown names are reported and then treated as no annotation at all
+    fn annotation_name(&mut self, annotation: &Option<Token>) -> Option<String> {
+        let token = annotation.as_ref()?;
+
+        if !TYPE_NAMES.contains(&token.lexeme.as_str()) {
+            self.errors.push(format!(
+                "Unknown type {} at line {}",
+                token.lexeme, token.line
+            ));
+            return None;
+        }
+
+        Some(token.lexeme.clone())
+    }
+
+    fn mismatch(expected: &str, actual: &Option<String>) -> bool {
+        matches!(actual, Some(actual) if actual != expected)
+    }
+
+    fn declare(&mut self, name: &str, binding: Binding) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(String::from(name), binding);
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name))
+            .cloned()
+    }
+}